@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nelf::{validate, NelfIter};
+
+fuzz_target!(|data: &[u8]| {
+    for (cell, _) in NelfIter::from_string(data).with_termination() {
+        let range = data.as_ptr_range();
+        assert!(range.start <= cell.as_ptr() && cell.as_ptr() <= range.end);
+    }
+
+    let _ = validate(data);
+});
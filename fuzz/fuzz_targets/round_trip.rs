@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nelf::{validate, NelfIter, ToNelf};
+
+fuzz_target!(|cells: Vec<Vec<u8>>| {
+    let list = cells.iter().to_nelf();
+
+    assert_eq!(NelfIter::from_string(&list).collect::<Vec<_>>(), cells);
+    assert_eq!(validate(&list), Ok(()));
+});
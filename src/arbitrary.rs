@@ -0,0 +1,39 @@
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::Nelf;
+
+impl<'a> Arbitrary<'a> for Nelf {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Nelf::from_cells(Vec::<Vec<u8>>::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<Vec<u8>>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::arbitrary::{Arbitrary, Unstructured};
+
+    use crate::{validate, Nelf, NelfIter};
+
+    #[test]
+    fn arbitrary_nelf() {
+        let data: Vec<u8> = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect();
+
+        for start in 0..256 {
+            let data = &data[start..];
+            let cells = Vec::<Vec<u8>>::arbitrary(&mut Unstructured::new(data))
+                .unwrap();
+            let nelf = Nelf::arbitrary(&mut Unstructured::new(data)).unwrap();
+            assert_eq!(validate(nelf.as_bytes()), Ok(()));
+            assert_eq!(
+                NelfIter::from_string(nelf.as_bytes()).collect::<Vec<_>>(),
+                cells
+            );
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use alloc::vec::Vec;
+
+use bumpalo::Bump;
+
+use crate::NelfIter;
+
+/// Decodes the cells of the list, copying their contents into the arena.
+///
+/// Useful when the source can't be borrowed for as long as the cells are
+/// needed, since the cells share the memory of the arena instead of being
+/// allocated separately.
+pub fn decode_into_arena<'a>(string: &[u8], arena: &'a Bump) -> Vec<&'a [u8]> {
+    NelfIter::from_string(string)
+        .map(|cell| &*arena.alloc_slice_copy(cell))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bumpalo::Bump;
+
+    use crate::ToNelf;
+
+    use super::decode_into_arena;
+
+    #[test]
+    fn decode_into_arena_1() {
+        let arena = Bump::new();
+        let string = [&b"A"[..], b"/|\\", b""].to_nelf();
+        let cells = decode_into_arena(&string, &arena);
+        drop(string);
+        assert_eq!(cells, [&b"A"[..], b"/|\\", b""]);
+    }
+}
@@ -0,0 +1,38 @@
+use ::arrayvec::{ArrayVec, CapacityError};
+
+use crate::wrap::{cell_len, wrapper, write_cell};
+
+/// Encodes the content as a NELF cell into an `ArrayVec` with the capacity
+/// of `N` bytes, without allocating.
+///
+/// Returns an error if the encoded cell doesn't fit into the capacity.
+pub fn to_cell_arrayvec<const N: usize>(
+    content: &[u8],
+) -> Result<ArrayVec<u8, N>, CapacityError> {
+    let wrapper = wrapper(content);
+    let len = match cell_len(content, wrapper) {
+        Some(len) if len <= N => len,
+        _ => return Err(CapacityError::new(())),
+    };
+
+    let mut cell = ArrayVec::from([0; N]);
+    cell.truncate(len);
+    write_cell(content, wrapper, &mut cell);
+
+    Ok(cell)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_cell_arrayvec;
+
+    #[test]
+    fn to_cell_arrayvec_1() {
+        let cell = to_cell_arrayvec::<16>(b"A|B/C").unwrap();
+        assert_eq!(&cell[..], b"/A|B/C\\");
+
+        let cell = to_cell_arrayvec::<7>(b"/|").unwrap();
+        assert_eq!(&cell[..], b"\\\\/|//");
+        assert!(to_cell_arrayvec::<5>(b"/|").is_err());
+    }
+}
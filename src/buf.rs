@@ -0,0 +1,234 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::IoSlice;
+use std::ops::Range;
+
+use bytes::{Buf, BufMut, Bytes};
+
+use crate::iter::CellScanner;
+use crate::wrap::wrapper;
+
+/// Error returned by [`decode_buf`] when the buffer holds more bytes than it
+/// exposes, and no complete cell was found in the exposed ones.
+///
+/// [`decode_buf`]: decode_buf
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BufError {
+    exposed: usize,
+}
+
+impl BufError {
+    /// Returns the number of bytes the buffer exposed, all of which were
+    /// scanned.
+    pub fn exposed(&self) -> usize {
+        self.exposed
+    }
+}
+
+impl Display for BufError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no complete cell in the {} bytes exposed by the buffer",
+            self.exposed,
+        )
+    }
+}
+
+impl Error for BufError {}
+
+/// Decodes the first cell contained in the buffer, advancing past it.
+///
+/// Returns `None` without consuming anything if the buffer doesn't contain a
+/// complete cell yet. Buffers made of several chunks are scanned chunk by
+/// chunk as far as [`Buf::chunks_vectored`] exposes them, since [`Buf`] has
+/// no other way to look past the first chunk without consuming it. Only the
+/// cell itself is copied.
+///
+/// Returns an error, again without consuming anything, if no complete cell
+/// is found in the exposed chunks while the buffer holds more bytes than
+/// that, which happens with buffers keeping the default implementation of
+/// [`Buf::chunks_vectored`] that only exposes the first chunk. Waiting for
+/// more bytes would then never help, so such buffers have to be copied into
+/// a contiguous one first.
+///
+/// [`Buf`]: bytes::Buf
+/// [`Buf::chunks_vectored`]: bytes::Buf::chunks_vectored
+pub fn decode_buf<B: Buf>(buf: &mut B) -> Result<Option<Bytes>, BufError> {
+    let Some((range, len)) = find_cell(buf)? else {
+        return Ok(None);
+    };
+
+    buf.advance(range.start);
+    let cell = buf.copy_to_bytes(range.len());
+    buf.advance(len - range.end);
+
+    Ok(Some(cell))
+}
+
+/// Encodes the content as a NELF cell, writing it into the buffer.
+pub fn encode_buf<B: BufMut>(cell: &[u8], buf: &mut B) {
+    let (open, close, len) = wrapper(cell);
+
+    buf.put_bytes(open, len);
+    buf.put_slice(cell);
+    buf.put_bytes(close, len);
+}
+
+fn find_cell<B: Buf>(
+    buf: &B,
+) -> Result<Option<(Range<usize>, usize)>, BufError> {
+    let mut scanner = CellScanner::default();
+    let mut exposed = buf.chunk().len();
+
+    if let Some(found) = scanner.feed(buf.chunk()) {
+        return Ok(Some(found));
+    }
+
+    let mut slices = vec![IoSlice::new(&[]); 4];
+    let mut scanned = 1;
+
+    loop {
+        let count = buf.chunks_vectored(&mut slices);
+
+        for slice in &slices[scanned.min(count)..count] {
+            exposed += slice.len();
+
+            if let Some(found) = scanner.feed(slice) {
+                return Ok(Some(found));
+            }
+        }
+
+        if count < slices.len() {
+            return if exposed < buf.remaining() {
+                Err(BufError { exposed })
+            } else {
+                Ok(None)
+            };
+        }
+
+        scanned = count;
+        slices.resize(slices.len() * 2, IoSlice::new(&[]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::io::IoSlice;
+
+    use bytes::{Buf, Bytes, BytesMut};
+
+    use crate::{NelfIter, ToCell};
+
+    use super::{decode_buf, encode_buf, BufError};
+
+    #[test]
+    fn decode_buf_1() {
+        let mut buf = Bytes::from_static(b"C||A|")
+            .chain(Bytes::from_static(b"A||C/B"))
+            .chain(Bytes::from_static(b"\\C|C"));
+        assert_eq!(decode_buf(&mut buf).unwrap().unwrap(), b"A|A"[..]);
+        assert_eq!(decode_buf(&mut buf).unwrap().unwrap(), b"B"[..]);
+        assert_eq!(decode_buf(&mut buf), Ok(None));
+        assert_eq!(buf.remaining(), 3);
+        assert_eq!(buf.chunk(), b"C|C");
+    }
+
+    /// Buffer made of fragments, all of which are exposed as slices.
+    struct Fragments(VecDeque<Bytes>);
+
+    impl Buf for Fragments {
+        fn remaining(&self) -> usize {
+            self.0.iter().map(Bytes::len).sum()
+        }
+
+        fn chunk(&self) -> &[u8] {
+            self.0.front().map_or(&[], |fragment| fragment)
+        }
+
+        fn advance(&mut self, mut cnt: usize) {
+            while cnt > 0 {
+                let fragment = self.0.front_mut().unwrap();
+                let len = cnt.min(fragment.len());
+                fragment.advance(len);
+                cnt -= len;
+
+                if fragment.is_empty() {
+                    self.0.pop_front();
+                }
+            }
+        }
+
+        fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+            let fragments =
+                self.0.iter().filter(|fragment| !fragment.is_empty());
+            let mut count = 0;
+
+            for (slice, fragment) in dst.iter_mut().zip(fragments) {
+                *slice = IoSlice::new(fragment);
+                count += 1;
+            }
+
+            count
+        }
+    }
+
+    #[test]
+    fn decode_buf_fragments() {
+        let fragments = [&b"C|"[..], b"|A", b"|", b"A|", b"|C/B", b"\\C|C"];
+        let mut buf =
+            Fragments(fragments.into_iter().map(Bytes::from_static).collect());
+        assert_eq!(decode_buf(&mut buf).unwrap().unwrap(), b"A|A"[..]);
+        assert_eq!(decode_buf(&mut buf).unwrap().unwrap(), b"B"[..]);
+        assert_eq!(decode_buf(&mut buf), Ok(None));
+        assert_eq!(buf.remaining(), 3);
+        assert_eq!(buf.chunk(), b"C|C");
+    }
+
+    /// Buffer exposing only its first fragment, like buffers keeping the
+    /// default implementation of [`Buf::chunks_vectored`].
+    struct FirstChunk(Fragments);
+
+    impl Buf for FirstChunk {
+        fn remaining(&self) -> usize {
+            self.0.remaining()
+        }
+
+        fn chunk(&self) -> &[u8] {
+            self.0.chunk()
+        }
+
+        fn advance(&mut self, cnt: usize) {
+            self.0.advance(cnt);
+        }
+    }
+
+    #[test]
+    fn decode_buf_first_chunk() {
+        let fragments = [&b"|A||B"[..], b"|C"];
+        let mut buf = FirstChunk(Fragments(
+            fragments.into_iter().map(Bytes::from_static).collect(),
+        ));
+        assert_eq!(decode_buf(&mut buf).unwrap().unwrap(), b"A"[..]);
+        assert_eq!(decode_buf(&mut buf), Err(BufError { exposed: 2 }));
+        assert_eq!(buf.remaining(), 4);
+
+        buf.advance(2);
+        assert_eq!(decode_buf(&mut buf), Ok(None));
+        assert_eq!(buf.remaining(), 2);
+    }
+
+    #[test]
+    fn encode_buf_1() {
+        let mut buf = BytesMut::new();
+        encode_buf(b"A", &mut buf);
+        encode_buf(b"/|", &mut buf);
+        encode_buf(b"", &mut buf);
+        assert_eq!(buf[..3], b"A".to_cell());
+        assert_eq!(
+            NelfIter::from_string(&buf).collect::<Vec<_>>(),
+            [&b"A"[..], b"/|", b""]
+        );
+    }
+}
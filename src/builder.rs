@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+
+use crate::encode_cell_append;
+use crate::wrap::wrapper;
+
+/// Builder of NELF lists whose cells may be nested lists themselves.
+///
+/// Nested lists are encoded directly into the buffer of the outer list. Once
+/// a nested list is ended, its opening run is inserted in front of it and its
+/// closing run is appended, so no separate buffer is allocated per level.
+#[derive(Clone, Debug, Default)]
+pub struct NelfBuilder {
+    buf: Vec<u8>,
+    starts: Vec<usize>,
+}
+
+impl NelfBuilder {
+    /// Construct the builder of an empty list.
+    pub fn new() -> Self {
+        NelfBuilder::default()
+    }
+
+    /// Appends the content as a cell to the innermost list being built.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the cell overflows `usize`, like
+    /// [`ToCell::to_cell`].
+    ///
+    /// [`ToCell::to_cell`]: crate::ToCell::to_cell
+    pub fn push_cell(&mut self, content: &[u8]) -> &mut Self {
+        encode_cell_append(content, &mut self.buf);
+        self
+    }
+
+    /// Starts a nested list, which becomes a cell of the current list once
+    /// it is ended.
+    pub fn begin_list(&mut self) -> &mut Self {
+        self.starts.push(self.buf.len());
+        self
+    }
+
+    /// Ends the innermost nested list, encoding it as a cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no nested list has been started.
+    pub fn end_list(&mut self) -> &mut Self {
+        let start = self.starts.pop().expect("no nested list to end");
+        let (open, close, len) = wrapper(&self.buf[start..]);
+
+        self.buf.resize(self.buf.len() + len, open);
+        self.buf[start..].rotate_right(len);
+        self.buf.resize(self.buf.len() + len, close);
+        self
+    }
+
+    /// Ends the nested lists that are still being built and returns the
+    /// encoded list.
+    pub fn finish(mut self) -> Vec<u8> {
+        while !self.starts.is_empty() {
+            self.end_list();
+        }
+
+        self.buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ToNelf;
+
+    use super::NelfBuilder;
+
+    #[test]
+    fn nelf_builder_1() {
+        let mut builder = NelfBuilder::new();
+        builder
+            .push_cell(b"A")
+            .begin_list()
+            .push_cell(b"B|")
+            .begin_list()
+            .push_cell(b"C")
+            .end_list()
+            .begin_list()
+            .end_list()
+            .end_list()
+            .push_cell(b"");
+
+        let inner = [b"C"].to_nelf();
+        let middle = [&b"B|"[..], &inner, b""].to_nelf();
+        let nested = [&b"A"[..], &middle, b""].to_nelf();
+        assert_eq!(builder.finish(), nested);
+    }
+
+    #[test]
+    fn nelf_builder_finish() {
+        let mut builder = NelfBuilder::new();
+        builder.begin_list().begin_list().push_cell(b"A");
+        assert_eq!(builder.finish(), [[[b"A"].to_nelf()].to_nelf()].to_nelf());
+    }
+}
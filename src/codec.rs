@@ -0,0 +1,141 @@
+use std::io;
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::iter::CellScanner;
+use crate::{encode_buf, NelfIter};
+
+/// Codec framing a byte stream as a list of NELF cells, one frame per cell.
+///
+/// Partially received cells are left in the buffer until the rest of them
+/// arrives. At the end of the stream the remaining data is parsed the same
+/// way [`NelfIter`] does.
+///
+/// The codec remembers how far it has scanned the buffer, so that the bytes
+/// of a large cell arriving in small packets are only scanned once.
+///
+/// [`NelfIter`]: crate::NelfIter
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NelfCodec {
+    scanner: CellScanner,
+}
+
+impl NelfCodec {
+    /// Construct the codec.
+    pub fn new() -> Self {
+        NelfCodec::default()
+    }
+}
+
+impl Decoder for NelfCodec {
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Vec<u8>>> {
+        let Some((range, len)) = self.scanner.scan(src) else {
+            return Ok(None);
+        };
+
+        let cell = src[range].to_vec();
+        src.advance(len);
+
+        Ok(Some(cell))
+    }
+
+    fn decode_eof(
+        &mut self,
+        buf: &mut BytesMut,
+    ) -> io::Result<Option<Vec<u8>>> {
+        if let Some(cell) = self.decode(buf)? {
+            return Ok(Some(cell));
+        }
+
+        let cell = NelfIter::from_string(buf).next().map(<[u8]>::to_vec);
+        buf.clear();
+        self.scanner = CellScanner::default();
+
+        Ok(cell)
+    }
+}
+
+impl Encoder<&[u8]> for NelfCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &[u8], dst: &mut BytesMut) -> io::Result<()> {
+        encode_buf(item, dst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use futures::{SinkExt, StreamExt, TryStreamExt};
+    use tokio::io::AsyncWriteExt;
+    use tokio_util::codec::{Decoder, Framed};
+
+    use crate::ToCell;
+
+    use super::NelfCodec;
+
+    #[tokio::test]
+    async fn nelf_codec_1() {
+        let (client, server) = tokio::io::duplex(4);
+
+        let writer = tokio::spawn(async move {
+            let mut client = Framed::new(client, NelfCodec::new());
+
+            for cell in [&b"A"[..], b"/|\\", b"", b"ABCDEFGH|I"] {
+                client.send(cell).await.unwrap();
+            }
+        });
+
+        let cells: Vec<_> = Framed::new(server, NelfCodec::new())
+            .try_collect()
+            .await
+            .unwrap();
+        writer.await.unwrap();
+
+        assert_eq!(cells, [&b"A"[..], b"/|\\", b"", b"ABCDEFGH|I"]);
+    }
+
+    #[tokio::test]
+    async fn nelf_codec_2() {
+        let (mut client, server) = tokio::io::duplex(64);
+
+        let writer = tokio::spawn(async move {
+            for packet in [&b"C||A|"[..], b"A||C/", b"B\\C|C"] {
+                client.write_all(packet).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let mut server = Framed::new(server, NelfCodec::new());
+        assert_eq!(server.next().await.unwrap().unwrap(), b"A|A");
+        assert_eq!(server.next().await.unwrap().unwrap(), b"B");
+        assert_eq!(server.next().await.unwrap().unwrap(), b"C");
+        assert!(server.next().await.is_none());
+        writer.await.unwrap();
+    }
+
+    #[test]
+    fn nelf_codec_large_cell() {
+        let content = b"A|B//C".repeat(50_000);
+        let cell = (&content).to_cell();
+        let mut codec = NelfCodec::new();
+        let mut buf = BytesMut::new();
+
+        for &byte in &cell[..cell.len() - 1] {
+            buf.extend_from_slice(&[byte]);
+            assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        }
+
+        buf.extend_from_slice(&cell[cell.len() - 1..]);
+        buf.extend_from_slice(b"|A");
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), content);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(b"|");
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), b"A");
+    }
+}
@@ -0,0 +1,156 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::text::str_cells;
+use crate::{encode_cell_append, NelfError};
+
+/// Error returned when the input is not a valid CSV row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CsvError {
+    offset: usize,
+}
+
+impl CsvError {
+    /// Returns the byte offset in the input at which the error was found.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid CSV row at byte {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CsvError {}
+
+/// Converts the list into a CSV row, one field per cell.
+///
+/// Fields containing commas, quotes or line breaks are quoted. Since an empty
+/// row is read back as a single empty field, an empty list doesn't
+/// round-trip. Returns an error if any of the cells is not valid UTF-8.
+pub fn nelf_to_csv_row(string: &[u8]) -> Result<String, NelfError> {
+    let mut result = String::new();
+
+    for (index, cell) in str_cells(string).enumerate() {
+        let cell = cell?;
+
+        if index != 0 {
+            result.push(',');
+        }
+
+        if cell.contains([',', '"', '\r', '\n']) {
+            result.push('"');
+            result.push_str(&cell.replace('"', "\"\""));
+            result.push('"');
+        } else {
+            result.push_str(cell);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Converts a CSV row into a NELF list, one cell per field.
+///
+/// Quoted fields may contain commas, line breaks and doubled quotes.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+pub fn csv_row_to_nelf(row: &str) -> Result<Vec<u8>, CsvError> {
+    let row = row.as_bytes();
+    let mut result = Vec::new();
+    let mut offset = 0;
+
+    loop {
+        let mut field = Vec::new();
+
+        if row.get(offset) == Some(&b'"') {
+            offset += 1;
+
+            loop {
+                match row.get(offset) {
+                    Some(b'"') if row.get(offset + 1) == Some(&b'"') => {
+                        field.push(b'"');
+                        offset += 2;
+                    }
+                    Some(b'"') => {
+                        offset += 1;
+                        break;
+                    }
+                    Some(&ch) => {
+                        field.push(ch);
+                        offset += 1;
+                    }
+                    None => return Err(CsvError { offset }),
+                }
+            }
+        } else {
+            while let Some(&ch) = row.get(offset) {
+                match ch {
+                    b',' => break,
+                    b'"' => return Err(CsvError { offset }),
+                    _ => field.push(ch),
+                }
+
+                offset += 1;
+            }
+        }
+
+        encode_cell_append(&field, &mut result);
+
+        match row.get(offset) {
+            Some(b',') => offset += 1,
+            Some(_) => return Err(CsvError { offset }),
+            None => return Ok(result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{NelfError, NelfIter, ToNelf};
+
+    use super::{csv_row_to_nelf, nelf_to_csv_row};
+
+    #[test]
+    fn csv_row_1() {
+        let nelf = [b"A", b"B", b"C"].to_nelf();
+        let row = nelf_to_csv_row(&nelf).unwrap();
+        assert_eq!(row, "A,B,C");
+        assert_eq!(csv_row_to_nelf(&row).unwrap(), nelf);
+        assert_eq!(
+            NelfIter::from_string(&csv_row_to_nelf(",").unwrap())
+                .collect::<Vec<_>>(),
+            [b"", b""]
+        );
+    }
+
+    #[test]
+    fn csv_row_2() {
+        let nelf = [&b"A,B"[..], b"\"C\"", b"D\nE", b"|"].to_nelf();
+        let row = nelf_to_csv_row(&nelf).unwrap();
+        assert_eq!(row, "\"A,B\",\"\"\"C\"\"\",\"D\nE\",|");
+        assert_eq!(csv_row_to_nelf(&row).unwrap(), nelf);
+    }
+
+    #[test]
+    fn csv_row_3() {
+        assert_eq!(
+            nelf_to_csv_row(b"|A||\xff|"),
+            Err(NelfError::InvalidUtf8 { index: 1 })
+        );
+        assert_eq!(csv_row_to_nelf("A,\"B").unwrap_err().offset(), 4);
+        assert_eq!(csv_row_to_nelf("A,\"B\"C").unwrap_err().offset(), 5);
+        assert_eq!(csv_row_to_nelf("A,B\"").unwrap_err().offset(), 3);
+    }
+}
@@ -0,0 +1,570 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::private::{ToCellSealed, ToNelfSealed};
+use crate::scan::IS_DELIM;
+use crate::wrap::{cell_len, encoded_len, wrapper, write_cell};
+use crate::NelfError;
+
+/// Trait used to encode strings as cells in a NELF list.
+///
+/// Cells are wrapped with the shortest runs of delimiters they can be decoded
+/// with, so the encoding is as short as possible. When several delimiters
+/// need equally long runs, pipes are preferred over slashes, and slashes
+/// over backslashes. The runs are never more than one byte longer than the
+/// longest run of delimiters in the content.
+///
+/// Already implemented for the most commonly used types, sealed.
+pub trait ToCell: ToCellSealed {
+    /// Encodes the value as a NELF cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the cell overflows `usize`, like growing a
+    /// `Vec` past its maximum capacity does, rather than wrapping around.
+    /// See [`try_encode_cell_append`] for a version returning an error
+    /// instead.
+    ///
+    /// [`try_encode_cell_append`]: try_encode_cell_append
+    fn to_cell(self) -> Vec<u8>;
+}
+
+/// Length of the stack buffer small cells are encoded in before being
+/// copied to the result.
+const STACK_CELL_LEN: usize = 128;
+
+/// Encodes the content as a cell, appending it to the result.
+pub(crate) fn encode_cell(content: &[u8], result: &mut Vec<u8>) {
+    let wrapper = wrapper(content);
+    let cell_len = cell_len(content, wrapper).expect("encoded length overflow");
+
+    if cell_len <= STACK_CELL_LEN {
+        let mut cell = [0; STACK_CELL_LEN];
+        write_cell(content, wrapper, &mut cell[..cell_len]);
+        result.extend_from_slice(&cell[..cell_len]);
+    } else {
+        let start = result.len();
+        result.resize(start + cell_len, 0);
+        write_cell(content, wrapper, &mut result[start..]);
+    }
+}
+
+/// Encodes the content as a cell directly at the end of the buffer.
+///
+/// Unlike [`ToCell::to_cell`], no intermediate buffer is used: space for the
+/// cell is reserved once, and the runs and the content are written into it in
+/// place. Nothing is allocated if the space has already been reserved.
+///
+/// # Panics
+///
+/// Panics if the length of the cell overflows `usize`, like
+/// [`ToCell::to_cell`].
+///
+/// [`ToCell::to_cell`]: ToCell::to_cell
+pub fn encode_cell_append(content: &[u8], out: &mut Vec<u8>) {
+    let wrapper = wrapper(content);
+    let start = out.len();
+    let len = cell_len(content, wrapper).expect("encoded length overflow");
+
+    out.reserve(len);
+    out.resize(start + len, 0);
+    write_cell(content, wrapper, &mut out[start..]);
+}
+
+/// Encodes the content as a cell directly at the end of the buffer, like
+/// [`encode_cell_append`], returning an error instead of panicking or
+/// aborting if the cell doesn't fit into memory.
+///
+/// Returns [`NelfError::CapacityOverflow`] if the length of the cell
+/// overflows `usize` or the space for it can't be reserved, in which case
+/// nothing is written.
+///
+/// [`NelfError::CapacityOverflow`]: NelfError::CapacityOverflow
+pub fn try_encode_cell_append(
+    content: &[u8],
+    out: &mut Vec<u8>,
+) -> Result<(), NelfError> {
+    let wrapper = wrapper(content);
+    let start = out.len();
+    let len = cell_len(content, wrapper).ok_or(NelfError::CapacityOverflow)?;
+
+    out.try_reserve(len)
+        .map_err(|_| NelfError::CapacityOverflow)?;
+    out.resize(start + len, 0);
+    write_cell(content, wrapper, &mut out[start..]);
+
+    Ok(())
+}
+
+/// Encodes content without delimiters as a cell wrapped in single pipes,
+/// skipping the analysis of runs.
+///
+/// Returns `None` if the content contains any delimiters or is empty, in
+/// which case [`ToCell::to_cell`] has to be used instead.
+///
+/// [`ToCell::to_cell`]: ToCell::to_cell
+pub fn encode_cell_ascii_fast(content: &[u8]) -> Option<Vec<u8>> {
+    if content.is_empty() || content.iter().any(|&ch| IS_DELIM[ch as usize]) {
+        return None;
+    }
+
+    let mut cell = Vec::with_capacity(content.len() + 2);
+    cell.push(b'|');
+    cell.extend_from_slice(content);
+    cell.push(b'|');
+
+    Some(cell)
+}
+
+impl ToCellSealed for &[u8] {
+    fn content(&self) -> &[u8] {
+        self
+    }
+}
+
+impl ToCell for &[u8] {
+    fn to_cell(self) -> Vec<u8> {
+        let mut result = Vec::new();
+        encode_cell(self, &mut result);
+        result
+    }
+}
+
+impl<const N: usize> ToCellSealed for &[u8; N] {
+    fn content(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> ToCell for &[u8; N] {
+    fn to_cell(self) -> Vec<u8> {
+        self.as_slice().to_cell()
+    }
+}
+
+impl<const N: usize> ToCellSealed for [u8; N] {
+    fn content(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> ToCell for [u8; N] {
+    fn to_cell(self) -> Vec<u8> {
+        self.as_slice().to_cell()
+    }
+}
+
+impl ToCellSealed for &Vec<u8> {
+    fn content(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl ToCell for &Vec<u8> {
+    fn to_cell(self) -> Vec<u8> {
+        self.as_slice().to_cell()
+    }
+}
+
+impl ToCellSealed for Vec<u8> {
+    fn content(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl ToCell for Vec<u8> {
+    fn to_cell(self) -> Vec<u8> {
+        self.as_slice().to_cell()
+    }
+}
+
+impl ToCellSealed for &str {
+    fn content(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl ToCell for &str {
+    fn to_cell(self) -> Vec<u8> {
+        self.as_bytes().to_cell()
+    }
+}
+
+impl ToCellSealed for String {
+    fn content(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl ToCell for String {
+    fn to_cell(self) -> Vec<u8> {
+        self.as_bytes().to_cell()
+    }
+}
+
+/// Trait used to convert containers of strings to NELF strings.
+///
+/// Already implemented for all iterables of byte slices. Sealed.
+pub trait ToNelf: ToNelfSealed {
+    /// Converts the list of strings into a NELF string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of any of the cells overflows `usize`, like
+    /// [`ToCell::to_cell`].
+    ///
+    /// [`ToCell::to_cell`]: ToCell::to_cell
+    fn to_nelf(self) -> Vec<u8>;
+}
+
+impl<T: IntoIterator<Item = V>, V: ToCell> ToNelfSealed for T {}
+
+impl<T: IntoIterator<Item = V>, V: ToCell> ToNelf for T {
+    fn to_nelf(self) -> Vec<u8> {
+        let mut result = Vec::new();
+
+        for string in self.into_iter() {
+            encode_cell(string.content(), &mut result);
+        }
+
+        result
+    }
+}
+
+/// Encodes the cells as a NELF list, like [`ToNelf::to_nelf`], allocating the
+/// result only once.
+///
+/// The iterator is cloned to compute the length of the result with
+/// [`encoded_len`] before encoding the cells.
+///
+/// # Panics
+///
+/// Panics if the length of the result overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: ToNelf::to_nelf
+/// [`encoded_len`]: encoded_len
+pub fn encode_list<I>(cells: I) -> Vec<u8>
+where
+    I: IntoIterator,
+    I::IntoIter: Clone,
+    I::Item: ToCell,
+{
+    let cells = cells.into_iter();
+    let len = cells
+        .clone()
+        .map(|string| encoded_len(string.content()))
+        .try_fold(0, usize::checked_add)
+        .expect("encoded length overflow");
+    let mut result = Vec::with_capacity(len);
+
+    for string in cells {
+        encode_cell(string.content(), &mut result);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{encoded_len, NelfIter};
+
+    use super::{
+        encode_cell_append, encode_cell_ascii_fast, encode_list,
+        try_encode_cell_append, ToCell, ToNelf,
+    };
+
+    #[test]
+    fn nelf_cell_1() {
+        assert_eq!(b"|".to_cell(), b"/|\\");
+        assert_eq!(b"/".to_cell(), b"|/|");
+        assert_eq!(b"\\".to_cell(), b"|\\|");
+        assert_eq!(b"||".to_cell(), b"/||\\");
+        assert_eq!(b"//".to_cell(), b"|//|");
+        assert_eq!(b"\\\\".to_cell(), b"|\\\\|");
+    }
+
+    #[test]
+    fn nelf_cell_2() {
+        assert_eq!(b"/|".to_cell(), b"\\\\/|//");
+        assert_eq!(b"\\|".to_cell(), b"//\\|\\\\");
+        assert_eq!(b"|/\\|".to_cell(), b"//|/\\|\\\\");
+        assert_eq!(b"/|/".to_cell(), b"||/|/||");
+    }
+
+    #[test]
+    fn nelf_cell_3() {
+        let fixtures: [(&[u8], &[u8]); 10] = [
+            (b"|", b"/|\\"),
+            (b"/", b"|/|"),
+            (b"\\", b"|\\|"),
+            (b"||", b"/||\\"),
+            (b"//", b"|//|"),
+            (b"\\\\", b"|\\\\|"),
+            (b"/|", b"\\\\/|//"),
+            (b"\\|", b"//\\|\\\\"),
+            (b"|/\\|", b"//|/\\|\\\\"),
+            (b"/|/", b"||/|/||"),
+        ];
+
+        for (content, cell) in fixtures {
+            let len = (cell.len() - content.len()) / 2;
+            let large = [content, b"A"].concat().repeat(100);
+            let large = [&large[..], content].concat();
+            let large_cell = [&cell[..len], &large, &cell[cell.len() - len..]];
+
+            assert_eq!(content.to_cell(), cell);
+            assert_eq!([b"A", content].to_nelf(), [&b"|A|"[..], cell].concat());
+            assert_eq!((&large).to_cell(), large_cell.concat());
+        }
+    }
+
+    #[test]
+    fn nelf_cell_lengths() {
+        for n in [0, 1, 63, 64, 125, 126, 127, 128, 1000] {
+            let content = b"A|".repeat(n / 2 + 1)[..n].to_vec();
+            let cell = (&content).to_cell();
+            assert_eq!(cell.len(), encoded_len(&content));
+            assert_eq!(NelfIter::from_string(&cell).next().unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn round_trip_delimiters() {
+        let alphabet = [b'|', b'/', b'\\'];
+
+        for len in 1..=8 {
+            for mut code in 0..alphabet.len().pow(len) {
+                let content: Vec<u8> = (0..len)
+                    .map(|_| {
+                        let ch = alphabet[code % alphabet.len()];
+                        code /= alphabet.len();
+                        ch
+                    })
+                    .collect();
+
+                let cell = (&content).to_cell();
+                assert_eq!(
+                    NelfIter::from_string(&cell).collect::<Vec<_>>(),
+                    [&content],
+                );
+
+                let list = [&b"A"[..], &content, &content, b""].to_nelf();
+                assert_eq!(
+                    NelfIter::from_string(&list).collect::<Vec<_>>(),
+                    [&b"A"[..], &content, &content, b""],
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn nelf_cell_single_byte() {
+        for ch in 0..=u8::MAX {
+            let cell = [ch].to_cell();
+            // Only a pipe disqualifies pipes, as it is both the first and the
+            // last byte of the content.
+            let expected = if ch == b'|' {
+                *b"/|\\"
+            } else {
+                [b'|', ch, b'|']
+            };
+
+            assert_eq!(cell, expected, "{ch}");
+            assert_eq!(
+                NelfIter::from_string(&cell).collect::<Vec<_>>(),
+                [[ch]],
+                "{ch}"
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_interior_pipes() {
+        for content in [&b"a|b"[..], b"a||b", b"a|b||c", b"|a||b|"] {
+            let cell = content.to_cell();
+            assert_eq!(
+                NelfIter::from_string(&cell).collect::<Vec<_>>(),
+                [content]
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_mixed_slashes() {
+        let contents: [&[u8]; 6] = [
+            b"/\\/\\",
+            b"\\\\//",
+            b"/|\\",
+            b"//\\\\/",
+            b"\\/A/\\",
+            b"|//\\\\|",
+        ];
+
+        for content in contents {
+            let cell = content.to_cell();
+            assert_eq!(
+                NelfIter::from_string(&cell).collect::<Vec<_>>(),
+                [content]
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_interior_runs() {
+        let runs: [&[u8]; 8] = [
+            b"/\\",
+            b"\\/",
+            b"||/",
+            b"/\\\\/",
+            b"|/|\\|",
+            b"///\\\\\\",
+            b"\\\\||//",
+            b"|\\//\\|",
+        ];
+
+        for run in runs {
+            let contents = [
+                run.to_vec(),
+                [b"A", run].concat(),
+                [run, b"A"].concat(),
+                [b"A", run, b"A"].concat(),
+                [run, b"A", run].concat(),
+                [b"A", run, run, b"A"].concat(),
+            ];
+
+            for content in contents {
+                let cell = (&content).to_cell();
+                let len = (cell.len() - content.len()) / 2;
+                assert_eq!(cell[len..cell.len() - len], content);
+                assert_eq!(
+                    NelfIter::from_string(&cell).collect::<Vec<_>>(),
+                    [&content]
+                );
+
+                let list = [run, &content, b"A"].to_nelf();
+                assert_eq!(
+                    NelfIter::from_string(&list).collect::<Vec<_>>(),
+                    [run, &content, b"A"]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_binary() {
+        let contents: [&[u8]; 8] = [
+            b"\0",
+            b"\0\0\0",
+            b"\xFF",
+            &[0xFF; 40],
+            b"\0|\xFF",
+            b"|\0/\xFF\\",
+            b"\xFF||\0//\xFF\\\\\0",
+            b"/\0\xFE\x80\x7F\x01|",
+        ];
+        let all: Vec<u8> = (0..=u8::MAX).collect();
+
+        for content in contents.into_iter().chain([&all[..]]) {
+            let cell = content.to_cell();
+            assert_eq!(
+                NelfIter::from_string(&cell).collect::<Vec<_>>(),
+                [content]
+            );
+        }
+
+        let list = contents.to_nelf();
+        assert_eq!(NelfIter::from_string(&list).collect::<Vec<_>>(), contents);
+    }
+
+    #[test]
+    fn round_trip_nested_empty() {
+        let empty = Vec::<&[u8]>::new().to_nelf();
+        assert_eq!(empty, b"");
+        assert_eq!([&empty].to_nelf(), b"/\\");
+
+        let mut list = empty;
+
+        for depth in 1..=5 {
+            list = [&list].to_nelf();
+            let mut inner = &list[..];
+
+            for _ in 0..depth {
+                let cells: Vec<_> = NelfIter::from_string(inner).collect();
+                assert_eq!(cells.len(), 1);
+                inner = cells[0];
+            }
+
+            assert_eq!(inner, b"");
+            assert_eq!(NelfIter::from_string(inner).next(), None);
+        }
+
+        let inner = [&b""[..], b"A", b""].to_nelf();
+        let list = [&b""[..], &inner, b""].to_nelf();
+        let cells: Vec<_> = NelfIter::from_string(&list).collect();
+        assert_eq!(cells, [&b""[..], &inner, b""]);
+        assert_eq!(
+            NelfIter::from_string(cells[1]).collect::<Vec<_>>(),
+            [&b""[..], b"A", b""]
+        );
+    }
+
+    #[test]
+    fn encode_cell_append_1() {
+        let cells = [&b"A"[..], b"", b"/|\\", b"||", &[b'|'; 200]];
+        let mut out = b"C".to_vec();
+
+        for cell in cells {
+            encode_cell_append(cell, &mut out);
+        }
+
+        let expected: Vec<_> =
+            cells.iter().map(|cell| cell.to_cell()).collect();
+        assert_eq!(out, [&b"C"[..], &expected.concat()].concat());
+    }
+
+    #[test]
+    fn try_encode_cell_append_1() {
+        let cells = [&b"A"[..], b"", b"/|\\", b"||", &[b'|'; 200]];
+        let mut out = b"C".to_vec();
+
+        for cell in cells {
+            try_encode_cell_append(cell, &mut out).unwrap();
+        }
+
+        let mut expected = b"C".to_vec();
+
+        for cell in cells {
+            encode_cell_append(cell, &mut expected);
+        }
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn encode_cell_ascii_fast_1() {
+        for content in [&b"A"[..], b"Hello, world!", b"\tA-B"] {
+            assert_eq!(
+                encode_cell_ascii_fast(content).unwrap(),
+                content.to_cell()
+            );
+        }
+
+        for content in [&b""[..], b"A|B", b"/", b"A\\"] {
+            assert_eq!(encode_cell_ascii_fast(content), None);
+        }
+    }
+
+    #[test]
+    fn encode_list_1() {
+        let cells = [&b"A"[..], b"/|\\", b"", b"||A"];
+        assert_eq!(encode_list(cells), cells.to_nelf());
+        assert_eq!(encode_list(&[b"A".to_vec()]), [b"A"].to_nelf());
+
+        for cell in cells {
+            assert_eq!(encoded_len(cell), cell.to_cell().len());
+        }
+    }
+}
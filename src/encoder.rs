@@ -0,0 +1,67 @@
+use alloc::vec::Vec;
+
+use crate::encode::encode_cell;
+use crate::private::ToCellSealed;
+use crate::ToCell;
+
+/// Encoder of NELF lists reusing its buffer between lists.
+///
+/// Encoding many lists with [`ToNelf::to_nelf`] allocates a new buffer for
+/// each of them, while the encoder only grows its buffer when a list doesn't
+/// fit into it.
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+#[derive(Clone, Debug, Default)]
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    /// Construct the encoder with an empty buffer.
+    pub fn new() -> Self {
+        Encoder::default()
+    }
+
+    /// Encodes the cells as a NELF list, replacing the previously encoded
+    /// list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the list overflows `usize`, like
+    /// [`ToNelf::to_nelf`].
+    ///
+    /// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+    pub fn encode<I>(&mut self, cells: I) -> &[u8]
+    where
+        I: IntoIterator,
+        I::Item: ToCell,
+    {
+        self.buf.clear();
+
+        for string in cells {
+            encode_cell(string.content(), &mut self.buf);
+        }
+
+        &self.buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{NelfIter, ToNelf};
+
+    use super::Encoder;
+
+    #[test]
+    fn encoder_1() {
+        let mut encoder = Encoder::new();
+        let first = encoder.encode([&b"ABCDEF"[..], b"||", b"G"]).to_vec();
+        assert_eq!(first, [&b"ABCDEF"[..], b"||", b"G"].to_nelf());
+
+        let second = encoder.encode([b"H"]);
+        assert_eq!(second, [b"H"].to_nelf());
+        assert_eq!(NelfIter::from_string(second).collect::<Vec<_>>(), [b"H"]);
+    }
+}
@@ -0,0 +1,45 @@
+use core::fmt::{self, Display, Formatter};
+
+/// Error returned by the checked conversions of this crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NelfError {
+    /// The encoded list doesn't fit into memory.
+    CapacityOverflow,
+    /// The cell at the given index is not valid UTF-8.
+    InvalidUtf8 {
+        /// Index of the cell in the list.
+        index: usize,
+    },
+    /// The list contains an odd number of cells where pairs were expected.
+    OddCount {
+        /// Number of cells in the list.
+        count: usize,
+    },
+    /// The cell at the given index is missing its closing run.
+    Unterminated {
+        /// Index of the cell in the list.
+        index: usize,
+    },
+}
+
+impl Display for NelfError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NelfError::CapacityOverflow => {
+                f.write_str("encoded list exceeds the maximum capacity")
+            }
+            NelfError::InvalidUtf8 { index } => {
+                write!(f, "cell {index} is not valid UTF-8")
+            }
+            NelfError::OddCount { count } => {
+                write!(f, "expected pairs of cells, found {count} cells")
+            }
+            NelfError::Unterminated { index } => {
+                write!(f, "cell {index} is not terminated")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NelfError {}
@@ -0,0 +1,402 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{ptr, slice};
+
+use crate::{try_encode_cell_append, IncrementalParser, NelfIter};
+
+/// Opaque handle of an iterator over the cells of a list, used from C.
+///
+/// Created with [`nelf_iter_new`] and destroyed with [`nelf_iter_free`].
+///
+/// [`nelf_iter_new`]: nelf_iter_new
+/// [`nelf_iter_free`]: nelf_iter_free
+pub struct NelfIterHandle {
+    iter: NelfIter<'static>,
+}
+
+/// Creates an iterator over the cells of the list of `len` bytes at `ptr`.
+///
+/// The returned handle has to be destroyed with [`nelf_iter_free`].
+///
+/// # Safety
+///
+/// `ptr` has to point to `len` readable bytes, or may be null if `len` is 0.
+/// The list is borrowed rather than copied, so it has to stay valid and
+/// unmodified until the handle is destroyed.
+///
+/// [`nelf_iter_free`]: nelf_iter_free
+#[no_mangle]
+pub unsafe extern "C" fn nelf_iter_new(
+    ptr: *const u8,
+    len: usize,
+) -> *mut NelfIterHandle {
+    Box::into_raw(Box::new(NelfIterHandle {
+        iter: NelfIter::from_string(bytes(ptr, len)),
+    }))
+}
+
+/// Advances the iterator, writing the pointer to the next cell and its
+/// length to `out_ptr` and `out_len`.
+///
+/// Returns `false` without writing anything at the end of the list. The cell
+/// points into the list the iterator was created with.
+///
+/// # Safety
+///
+/// `handle` has to be a handle returned by [`nelf_iter_new`] that hasn't been
+/// destroyed yet, and `out_ptr` and `out_len` have to be valid for writes.
+///
+/// [`nelf_iter_new`]: nelf_iter_new
+#[no_mangle]
+pub unsafe extern "C" fn nelf_iter_next(
+    handle: *mut NelfIterHandle,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> bool {
+    match (*handle).iter.next() {
+        Some(cell) => {
+            *out_ptr = cell.as_ptr();
+            *out_len = cell.len();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Destroys the iterator. Does nothing if `handle` is null.
+///
+/// # Safety
+///
+/// `handle` has to be null or a handle returned by [`nelf_iter_new`] that
+/// hasn't been destroyed yet.
+///
+/// [`nelf_iter_new`]: nelf_iter_new
+#[no_mangle]
+pub unsafe extern "C" fn nelf_iter_free(handle: *mut NelfIterHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Encodes the `count` cells, the `i`th of which is `lens_ptr[i]` bytes at
+/// `cells_ptr[i]`, as a NELF list.
+///
+/// The list is written into a newly allocated buffer, the pointer to which
+/// and its length are written to `out_ptr_out` and `out_len_out`. The buffer
+/// is owned by the caller and has to be released with [`nelf_free`].
+///
+/// Returns 0 on success, or -1 if any of the pointers is null or the list
+/// doesn't fit into memory, in which case nothing is written to the output
+/// pointers.
+///
+/// # Safety
+///
+/// `cells_ptr` and `lens_ptr` have to point to `count` readable elements,
+/// unless `count` is 0. Each cell pointer has to point to as many readable
+/// bytes as its length, or may be null if the length is 0. `out_ptr_out` and
+/// `out_len_out` have to be valid for writes.
+///
+/// [`nelf_free`]: nelf_free
+#[no_mangle]
+pub unsafe extern "C" fn nelf_encode(
+    cells_ptr: *const *const u8,
+    lens_ptr: *const usize,
+    count: usize,
+    out_ptr_out: *mut *mut u8,
+    out_len_out: *mut usize,
+) -> i32 {
+    if out_ptr_out.is_null() || out_len_out.is_null() {
+        return -1;
+    }
+
+    if count != 0 && (cells_ptr.is_null() || lens_ptr.is_null()) {
+        return -1;
+    }
+
+    let (cells, lens) = if count == 0 {
+        (&[][..], &[][..])
+    } else {
+        (
+            slice::from_raw_parts(cells_ptr, count),
+            slice::from_raw_parts(lens_ptr, count),
+        )
+    };
+
+    if cells
+        .iter()
+        .zip(lens)
+        .any(|(cell, &len)| cell.is_null() && len != 0)
+    {
+        return -1;
+    }
+
+    let mut result = Vec::new();
+
+    for (&cell, &len) in cells.iter().zip(lens) {
+        if try_encode_cell_append(bytes(cell, len), &mut result).is_err() {
+            return -1;
+        }
+    }
+
+    write_buf(result, out_ptr_out, out_len_out);
+
+    0
+}
+
+/// Releases a buffer returned by [`nelf_encode`] or
+/// [`nelf_incremental_next`]. Does nothing if `ptr` is null.
+///
+/// # Safety
+///
+/// `ptr` has to be null or a buffer returned by one of those functions that
+/// hasn't been released yet, and `len` has to be its length.
+///
+/// [`nelf_encode`]: nelf_encode
+/// [`nelf_incremental_next`]: nelf_incremental_next
+#[no_mangle]
+pub unsafe extern "C" fn nelf_free(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len)));
+    }
+}
+
+/// Opaque handle of a parser of a list arriving in pieces, used from C.
+///
+/// Created with [`nelf_incremental_new`] and destroyed with
+/// [`nelf_incremental_free`].
+///
+/// [`nelf_incremental_new`]: nelf_incremental_new
+/// [`nelf_incremental_free`]: nelf_incremental_free
+pub struct NelfIncrementalHandle {
+    parser: IncrementalParser,
+}
+
+/// Creates an empty incremental parser.
+///
+/// The returned handle has to be destroyed with [`nelf_incremental_free`].
+///
+/// [`nelf_incremental_free`]: nelf_incremental_free
+#[no_mangle]
+pub extern "C" fn nelf_incremental_new() -> *mut NelfIncrementalHandle {
+    Box::into_raw(Box::new(NelfIncrementalHandle {
+        parser: IncrementalParser::new(),
+    }))
+}
+
+/// Appends the `len` bytes at `ptr` to the input of the parser.
+///
+/// The data is copied, so it doesn't have to outlive the call.
+///
+/// # Safety
+///
+/// `handle` has to be a handle returned by [`nelf_incremental_new`] that
+/// hasn't been destroyed yet. `ptr` has to point to `len` readable bytes, or
+/// may be null if `len` is 0.
+///
+/// [`nelf_incremental_new`]: nelf_incremental_new
+#[no_mangle]
+pub unsafe extern "C" fn nelf_incremental_feed(
+    handle: *mut NelfIncrementalHandle,
+    ptr: *const u8,
+    len: usize,
+) {
+    (*handle).parser.feed(bytes(ptr, len));
+}
+
+/// Takes out the next complete cell, writing the pointer to a newly
+/// allocated copy of it and its length to `out_ptr_out` and `out_len_out`.
+///
+/// Returns `false` without writing anything if more data has to be fed
+/// first. The buffer is owned by the caller and has to be released with
+/// [`nelf_free`].
+///
+/// # Safety
+///
+/// `handle` has to be a handle returned by [`nelf_incremental_new`] that
+/// hasn't been destroyed yet, and `out_ptr_out` and `out_len_out` have to be
+/// valid for writes.
+///
+/// [`nelf_free`]: nelf_free
+/// [`nelf_incremental_new`]: nelf_incremental_new
+#[no_mangle]
+pub unsafe extern "C" fn nelf_incremental_next(
+    handle: *mut NelfIncrementalHandle,
+    out_ptr_out: *mut *mut u8,
+    out_len_out: *mut usize,
+) -> bool {
+    match (*handle).parser.next_cell() {
+        Some(cell) => {
+            write_buf(cell, out_ptr_out, out_len_out);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Destroys the parser. Does nothing if `handle` is null.
+///
+/// # Safety
+///
+/// `handle` has to be null or a handle returned by [`nelf_incremental_new`]
+/// that hasn't been destroyed yet.
+///
+/// [`nelf_incremental_new`]: nelf_incremental_new
+#[no_mangle]
+pub unsafe extern "C" fn nelf_incremental_free(
+    handle: *mut NelfIncrementalHandle,
+) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Borrows the `len` bytes at `ptr`, which may be null if `len` is 0.
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// Hands the buffer over to the caller, to be released with [`nelf_free`].
+///
+/// [`nelf_free`]: nelf_free
+unsafe fn write_buf(buf: Vec<u8>, out_ptr: *mut *mut u8, out_len: *mut usize) {
+    let buf = Box::into_raw(buf.into_boxed_slice());
+    *out_ptr = buf.cast();
+    *out_len = buf.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::{ptr, slice};
+
+    use crate::{NelfIter, ToNelf};
+
+    use super::{
+        nelf_encode, nelf_free, nelf_incremental_feed, nelf_incremental_free,
+        nelf_incremental_new, nelf_incremental_next, nelf_iter_free,
+        nelf_iter_new, nelf_iter_next, NelfIncrementalHandle, NelfIterHandle,
+    };
+
+    #[test]
+    fn nelf_iter_ffi_1() {
+        let new: unsafe extern "C" fn(*const u8, usize) -> *mut NelfIterHandle =
+            nelf_iter_new;
+        let next: unsafe extern "C" fn(
+            *mut NelfIterHandle,
+            *mut *const u8,
+            *mut usize,
+        ) -> bool = nelf_iter_next;
+        let free: unsafe extern "C" fn(*mut NelfIterHandle) = nelf_iter_free;
+
+        let string = [&b"A"[..], b"/|\\", b""].to_nelf();
+        let mut cells = Vec::new();
+
+        unsafe {
+            let handle = new(string.as_ptr(), string.len());
+            let mut cell = ptr::null();
+            let mut len = 0;
+
+            while next(handle, &mut cell, &mut len) {
+                cells.push(slice::from_raw_parts(cell, len));
+            }
+
+            free(handle);
+            free(ptr::null_mut());
+
+            let handle = new(ptr::null(), 0);
+            assert!(!next(handle, &mut cell, &mut len));
+            free(handle);
+        }
+
+        assert_eq!(cells, [&b"A"[..], b"/|\\", b""]);
+    }
+
+    #[test]
+    fn nelf_encode_ffi_1() {
+        let encode: unsafe extern "C" fn(
+            *const *const u8,
+            *const usize,
+            usize,
+            *mut *mut u8,
+            *mut usize,
+        ) -> i32 = nelf_encode;
+        let free: unsafe extern "C" fn(*mut u8, usize) = nelf_free;
+
+        let cells = [&b"A"[..], b"/|\\", b""];
+        let ptrs = cells.map(<[u8]>::as_ptr);
+        let lens = cells.map(<[u8]>::len);
+        let mut out = ptr::null_mut();
+        let mut len = 0;
+
+        unsafe {
+            let code =
+                encode(ptrs.as_ptr(), lens.as_ptr(), 3, &mut out, &mut len);
+            assert_eq!(code, 0);
+
+            let string = slice::from_raw_parts(out, len);
+            assert_eq!(string, cells.to_nelf());
+            assert_eq!(
+                NelfIter::from_string(string).collect::<Vec<_>>(),
+                cells
+            );
+            free(out, len);
+
+            assert_eq!(
+                encode(ptr::null(), ptr::null(), 0, &mut out, &mut len),
+                0
+            );
+            assert_eq!(len, 0);
+            free(out, len);
+
+            let code =
+                encode(ptr::null(), lens.as_ptr(), 3, &mut out, &mut len);
+            assert_eq!(code, -1);
+        }
+    }
+
+    #[test]
+    fn nelf_incremental_ffi_1() {
+        let new: extern "C" fn() -> *mut NelfIncrementalHandle =
+            nelf_incremental_new;
+        let feed: unsafe extern "C" fn(
+            *mut NelfIncrementalHandle,
+            *const u8,
+            usize,
+        ) = nelf_incremental_feed;
+        let next: unsafe extern "C" fn(
+            *mut NelfIncrementalHandle,
+            *mut *mut u8,
+            *mut usize,
+        ) -> bool = nelf_incremental_next;
+        let free: unsafe extern "C" fn(*mut NelfIncrementalHandle) =
+            nelf_incremental_free;
+
+        let mut cells = Vec::new();
+
+        unsafe {
+            let handle = new();
+            let mut cell = ptr::null_mut();
+            let mut len = 0;
+
+            for chunk in [&b"C||A|"[..], b"A||C/", b"", b"B\\C"] {
+                feed(handle, chunk.as_ptr(), chunk.len());
+
+                while next(handle, &mut cell, &mut len) {
+                    cells.push(slice::from_raw_parts(cell, len).to_vec());
+                    nelf_free(cell, len);
+                }
+            }
+
+            feed(handle, ptr::null(), 0);
+            assert!(!next(handle, &mut cell, &mut len));
+            free(handle);
+            free(ptr::null_mut());
+        }
+
+        assert_eq!(cells, [&b"A|A"[..], b"B"]);
+    }
+}
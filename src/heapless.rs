@@ -0,0 +1,42 @@
+use ::heapless::Vec;
+
+use crate::wrap::{cell_len, wrapper, write_cell};
+
+/// Encodes the content as a NELF cell into a `heapless::Vec` with the
+/// capacity of `N` bytes, without allocating.
+///
+/// Returns `Err` if the encoded cell doesn't fit into the capacity.
+#[allow(clippy::result_unit_err)]
+pub fn to_cell_heapless<const N: usize>(
+    content: &[u8],
+) -> Result<Vec<u8, N>, ()> {
+    let wrapper = wrapper(content);
+    let mut cell = Vec::new();
+
+    cell.resize(cell_len(content, wrapper).ok_or(())?, 0)?;
+    write_cell(content, wrapper, &mut cell);
+
+    Ok(cell)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_cell_heapless;
+
+    #[test]
+    fn to_cell_heapless_1() {
+        let fixtures: [(&[u8], &[u8]); 4] = [
+            (b"A", b"|A|"),
+            (b"", b"/\\"),
+            (b"/|", b"\\\\/|//"),
+            (b"||A", b"/||A\\"),
+        ];
+
+        for (content, cell) in fixtures {
+            assert_eq!(to_cell_heapless::<16>(content).unwrap(), cell);
+        }
+
+        assert_eq!(to_cell_heapless::<3>(b"A").unwrap(), b"|A|");
+        assert_eq!(to_cell_heapless::<2>(b"A"), Err(()));
+    }
+}
@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use crate::iter::CellScanner;
+
+/// Parser of cells contained in an encoded list arriving in pieces.
+///
+/// Data is fed to the parser as it becomes available, and cells are taken
+/// out of it once their closing run has been fed.
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalParser {
+    buf: Vec<u8>,
+    pos: usize,
+    scanner: CellScanner,
+}
+
+/// State of an [`IncrementalParser`], used to resume parsing later.
+///
+/// [`IncrementalParser`]: IncrementalParser
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ParserState {
+    buf: Vec<u8>,
+    pos: usize,
+    scanner: CellScanner,
+}
+
+impl IncrementalParser {
+    /// Construct an empty parser.
+    pub fn new() -> Self {
+        IncrementalParser::default()
+    }
+
+    /// Appends the data to the input of the parser.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Takes out the next complete cell, returning `None` if more data has to
+    /// be fed first.
+    pub fn next_cell(&mut self) -> Option<Vec<u8>> {
+        let (range, len) = self.scanner.scan(&self.buf[self.pos..])?;
+        let cell = self.buf[self.pos..][range].to_vec();
+        self.pos += len;
+        Some(cell)
+    }
+
+    /// Captures the data fed to the parser that hasn't been taken out as
+    /// cells yet.
+    pub fn snapshot(&self) -> ParserState {
+        ParserState {
+            buf: self.buf.clone(),
+            pos: self.pos,
+            scanner: self.scanner,
+        }
+    }
+
+    /// Replaces the state of the parser with a previously captured one.
+    pub fn restore(&mut self, state: ParserState) {
+        self.buf = state.buf;
+        self.pos = state.pos;
+        self.scanner = state.scanner;
+    }
+}
+
+impl Iterator for IncrementalParser {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_cell()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ToCell;
+
+    use super::IncrementalParser;
+
+    #[test]
+    fn incremental_parser_1() {
+        let mut parser = IncrementalParser::new();
+        parser.feed(b"C||A|");
+        assert_eq!(parser.next_cell(), None);
+        parser.feed(b"A||C/B");
+        assert_eq!(parser.next_cell().unwrap(), b"A|A");
+        assert_eq!(parser.next_cell(), None);
+        parser.feed(b"\\C");
+        assert_eq!(parser.next_cell().unwrap(), b"B");
+        assert_eq!(parser.next_cell(), None);
+    }
+
+    #[test]
+    fn incremental_parser_snapshot() {
+        let mut parser = IncrementalParser::new();
+        parser.feed(b"|A||B");
+        assert_eq!(parser.next_cell().unwrap(), b"A");
+        let state = parser.snapshot();
+        drop(parser);
+
+        let mut parser = IncrementalParser::new();
+        parser.restore(state);
+        parser.feed(b"C|");
+        assert_eq!(parser.next_cell().unwrap(), b"BC");
+        assert_eq!(parser.next_cell(), None);
+    }
+
+    #[test]
+    fn incremental_parser_large_cell() {
+        let content = b"A|B//C".repeat(50_000);
+        let cell = (&content).to_cell();
+        let mut parser = IncrementalParser::new();
+
+        for byte in cell.chunks(1) {
+            assert_eq!(parser.next_cell(), None);
+            parser.feed(byte);
+        }
+
+        assert_eq!(parser.next_cell().unwrap(), content);
+        assert_eq!(parser.next_cell(), None);
+    }
+}
@@ -0,0 +1,242 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::ops::Range;
+
+use crate::iter::CellScanner;
+use crate::{NelfIter, ToCell};
+
+const CHUNK_SIZE: usize = 8192;
+
+/// Reader of cells contained in an encoded list coming from a [`Read`]
+/// source.
+///
+/// Unlike [`NelfIter`], the cells are copied out of an internal buffer, since
+/// the source is consumed incrementally. At the end of the source the
+/// remaining data is parsed the same way [`NelfIter`] does.
+///
+/// [`Read`]: std::io::Read
+/// [`NelfIter`]: crate::NelfIter
+pub struct NelfReader<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+    scanner: CellScanner,
+    eof: bool,
+}
+
+impl<R: Read> NelfReader<R> {
+    /// Construct the reader consuming the given source.
+    pub fn new(reader: R) -> Self {
+        NelfReader {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+            scanner: CellScanner::default(),
+            eof: false,
+        }
+    }
+
+    /// Reads the next cell, returning `None` at the end of the list.
+    pub fn read_cell(&mut self) -> io::Result<Option<Vec<u8>>> {
+        Ok(self.next_range()?.map(|range| self.buf[range].to_vec()))
+    }
+
+    /// Advances past `n` cells without copying their contents.
+    ///
+    /// Returns an error of kind [`UnexpectedEof`] if the list contains less
+    /// than `n` cells.
+    ///
+    /// [`UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+    pub fn skip_cells(&mut self, n: usize) -> io::Result<()> {
+        for _ in 0..n {
+            if self.next_range()?.is_none() {
+                return Err(ErrorKind::UnexpectedEof.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unwraps the reader, returning the underlying source.
+    ///
+    /// Data that has already been read into the internal buffer is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn next_range(&mut self) -> io::Result<Option<Range<usize>>> {
+        loop {
+            let string = &self.buf[self.pos..];
+            let offset = self.pos;
+
+            if let Some((range, len)) = self.scanner.scan(string) {
+                self.pos += len;
+                return Ok(Some(offset + range.start..offset + range.end));
+            }
+
+            if self.eof {
+                let range = NelfIter::from_string(string).next_range();
+                self.pos = self.buf.len();
+                self.scanner = CellScanner::default();
+                return Ok(range.map(|(range, _)| {
+                    offset + range.start..offset + range.end
+                }));
+            }
+
+            self.fill()?;
+        }
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+
+        let mut chunk = [0; CHUNK_SIZE];
+
+        loop {
+            match self.reader.read(&mut chunk) {
+                Ok(0) => {
+                    self.eof = true;
+                    return Ok(());
+                }
+                Ok(read) => {
+                    self.buf.extend_from_slice(&chunk[..read]);
+                    return Ok(());
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => (),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for NelfReader<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_cell().transpose()
+    }
+}
+
+/// Encodes the content as a NELF cell and writes it to the stream, for
+/// example a [`TcpStream`].
+///
+/// # Panics
+///
+/// Panics if the length of the cell overflows `usize`, like
+/// [`ToCell::to_cell`].
+///
+/// [`TcpStream`]: std::net::TcpStream
+/// [`ToCell::to_cell`]: crate::ToCell::to_cell
+pub fn send_cell<W: Write>(stream: &mut W, cell: &[u8]) -> io::Result<()> {
+    stream.write_all(&cell.to_cell())
+}
+
+/// Receives the next cell from the stream, for example a [`TcpStream`].
+///
+/// `buf` holds the data read past the end of the returned cell and has to be
+/// passed again to the next call on the same stream. Returns `None` once the
+/// stream is closed and no cells are left in the buffer.
+///
+/// [`TcpStream`]: std::net::TcpStream
+pub fn recv_cell<R: Read>(
+    stream: &mut R,
+    buf: &mut Vec<u8>,
+) -> io::Result<Option<Vec<u8>>> {
+    let mut chunk = [0; CHUNK_SIZE];
+    let mut scanner = CellScanner::default();
+
+    loop {
+        if let Some((range, len)) = scanner.scan(buf) {
+            let cell = buf[range].to_vec();
+            buf.drain(..len);
+            return Ok(Some(cell));
+        }
+
+        match stream.read(&mut chunk) {
+            Ok(0) => {
+                let cell =
+                    NelfIter::from_string(buf).next().map(<[u8]>::to_vec);
+                buf.clear();
+                return Ok(cell);
+            }
+            Ok(read) => buf.extend_from_slice(&chunk[..read]),
+            Err(error) if error.kind() == ErrorKind::Interrupted => (),
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use crate::ToCell;
+
+    use super::{recv_cell, send_cell, NelfReader};
+
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(1);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn nelf_reader_1() {
+        let reader = NelfReader::new(Trickle(b"C||A||C/B\\C|C"));
+        assert_eq!(
+            reader.collect::<io::Result<Vec<_>>>().unwrap(),
+            [b"A", b"B", b"C"]
+        );
+    }
+
+    #[test]
+    fn nelf_reader_large_cell() {
+        let content = b"A|B//C".repeat(50_000);
+        let string = [(&content).to_cell(), b"|A|".to_vec()].concat();
+        let mut reader = NelfReader::new(Trickle(&string));
+        assert_eq!(reader.read_cell().unwrap().unwrap(), content);
+        assert_eq!(reader.read_cell().unwrap().unwrap(), b"A");
+        assert_eq!(reader.read_cell().unwrap(), None);
+    }
+
+    #[test]
+    fn nelf_reader_skip_cells() {
+        let mut reader = NelfReader::new(Trickle(b"|A|/B|\\||C||"));
+        reader.skip_cells(2).unwrap();
+        assert_eq!(reader.read_cell().unwrap().unwrap(), b"C");
+        assert_eq!(reader.read_cell().unwrap(), None);
+        assert!(NelfReader::new(Trickle(b"|A|")).skip_cells(2).is_err());
+    }
+
+    #[test]
+    fn tcp_stream_1() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let sender = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+
+            for cell in [&b"A"[..], b"/|\\", b"", b"B"] {
+                send_cell(&mut stream, cell).unwrap();
+            }
+        });
+
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = Vec::new();
+        let mut cells = Vec::new();
+
+        while let Some(cell) = recv_cell(&mut stream, &mut buf).unwrap() {
+            cells.push(cell);
+        }
+
+        sender.join().unwrap();
+        assert_eq!(cells, [&b"A"[..], b"/|\\", b"", b"B"]);
+    }
+}
@@ -0,0 +1,745 @@
+use core::ops::Range;
+
+use crate::scan::{find_byte, find_delimiter, IS_DELIM};
+use crate::NelfError;
+
+/// Iterator of cells contained in the encoded list.
+///
+/// Borrows the source and iterates over slices of that source, so cells are
+/// never copied, and outlive the iterator.
+///
+/// If the closing run of the last cell is missing, the rest of the source is
+/// yielded as its contents, the same as if it was terminated, so `|A` yields
+/// `A` just like `|A|`. [`NelfIter::with_termination`] tells such cells
+/// apart, and [`validate`] rejects lists containing them.
+///
+/// An opening run that reaches the end of the source is not followed by any
+/// contents, so no cell is yielded for it. A list made of a single run, like
+/// `||` or `||||`, thus yields nothing, since a run of pipes is read as one
+/// opening run rather than as one run closing another. That's why empty cells
+/// are encoded as `/\`, which, like `//\\`, yields an empty cell because
+/// its opening run is directly followed by its closing run. [`validate`]
+/// rejects lists ending with an opening run.
+///
+/// A cell is closed by the first run of its closing delimiter as long as its
+/// opening run, and the delimiters directly following it start the next
+/// cell. So in `|A||B|` the second pipe opens `B`, while in `|A|||B|` the
+/// leftover `||` does, leaving `B|` unterminated. Back-to-back runs of pipes
+/// thus never yield empty cells between the cells around them, only an
+/// opening run directly followed by a different closing run, like `/\`, does.
+///
+/// [`NelfIter::with_termination`]: NelfIter::with_termination
+/// [`validate`]: validate
+#[derive(Clone, Copy)]
+pub struct NelfIter<'a> {
+    string: &'a [u8],
+    index: usize,
+    remaining: Option<usize>,
+}
+
+impl<'a> NelfIter<'a> {
+    /// Construct the iterator borrowing from the encoded list.
+    pub fn from_string(string: &'a [u8]) -> Self {
+        NelfIter {
+            string,
+            index: 0,
+            remaining: None,
+        }
+    }
+
+    /// Construct the iterator over a list known to hold exactly `len` cells,
+    /// which it then reports as its exact size.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_len(string: &'a [u8], len: usize) -> Self {
+        NelfIter {
+            string,
+            index: 0,
+            remaining: Some(len),
+        }
+    }
+
+    /// Adapts the iterator to yield whether each cell is terminated by its
+    /// closing run along with the cell.
+    ///
+    /// Only the last cell of a list can be unterminated, since it then
+    /// extends to the end of the source.
+    pub fn with_termination(self) -> WithTermination<'a> {
+        WithTermination { iter: self }
+    }
+}
+
+impl<'a> NelfIter<'a> {
+    /// Returns the position in the source past the cells iterated over.
+    pub(crate) fn consumed(&self) -> usize {
+        self.index
+    }
+
+    /// Advances past the next cell, returning the range of its contents in
+    /// the source and whether its closing run was found.
+    pub(crate) fn next_range(&mut self) -> Option<(Range<usize>, bool)> {
+        self.index += find_delimiter(&self.string[self.index..])?;
+        let lch = self.string[self.index];
+
+        let len = self.string[self.index..]
+            .iter()
+            .enumerate()
+            .find(|&(_, &ch)| ch != lch)
+            .map(|(index, _)| index)?;
+
+        let start = self.index + len;
+
+        let rch = closing(lch);
+
+        let mut index = start;
+
+        while let Some(offset) = find_byte(&self.string[index..], rch) {
+            index += offset;
+
+            let count = self.string[index..]
+                .iter()
+                .take(len)
+                .take_while(|&&ch| ch == rch)
+                .count();
+
+            if count == len {
+                self.index = index + len;
+                self.take_one();
+                return Some((start..index, true));
+            }
+
+            index += count;
+        }
+
+        self.index = self.string.len();
+        self.take_one();
+        Some((start..self.string.len(), false))
+    }
+
+    /// Counts a cell off the known number of remaining cells, if any.
+    fn take_one(&mut self) {
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= 1;
+        }
+    }
+}
+
+impl<'a> Iterator for NelfIter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_range().map(|(range, _)| &self.string[range])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if let Some(remaining) = self.remaining {
+            return (remaining, Some(remaining));
+        }
+
+        // Every cell occupies at least two bytes of the source. A delimiter
+        // at the start of the rest whose run doesn't reach its end opens a
+        // cell, which is all that can be told without finding the cells.
+        let rest = &self.string[self.index..];
+        let lower = match (rest.first(), rest.last()) {
+            (Some(&first), Some(&last)) => {
+                usize::from(IS_DELIM[first as usize] && first != last)
+            }
+            _ => 0,
+        };
+        (lower, Some(rest.len() / 2))
+    }
+}
+
+/// Iterator returned by [`NelfIter::with_termination`], yielding the cells
+/// along with whether they are terminated.
+///
+/// [`NelfIter::with_termination`]: NelfIter::with_termination
+#[derive(Clone, Copy)]
+pub struct WithTermination<'a> {
+    iter: NelfIter<'a>,
+}
+
+impl<'a> Iterator for WithTermination<'a> {
+    type Item = (&'a [u8], bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (range, terminated) = self.iter.next_range()?;
+        Some((&self.iter.string[range], terminated))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Encoded list borrowed from a source.
+///
+/// Distinguishes an encoded list from arbitrary bytes without copying it,
+/// like `Nelf` does for owned lists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NelfStr<'a>(&'a [u8]);
+
+impl<'a> NelfStr<'a> {
+    /// Returns the encoded list.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Iterates over the cells of the list.
+    pub fn iter(&self) -> NelfIter<'a> {
+        NelfIter::from_string(self.0)
+    }
+
+    /// Returns the number of cells in the list.
+    ///
+    /// The cells are counted by parsing the whole list.
+    pub fn len_cells(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns the cell at the index, or `None` if the list is shorter.
+    ///
+    /// The list is parsed up to the cell.
+    pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+        self.iter().nth(index)
+    }
+
+    /// Returns the first cell, or `None` if the list is empty.
+    pub fn first(&self) -> Option<&'a [u8]> {
+        self.iter().next()
+    }
+
+    /// Returns the last cell, or `None` if the list is empty.
+    ///
+    /// The whole list is parsed from the start, so this takes time linear in
+    /// the length of the list, like [`NelfStr::len_cells`]. It can't be
+    /// scanned backwards, since whether a run of delimiters closes a cell
+    /// depends on where the cells before it begin, as in `/A|B|`, whose only
+    /// cell is the unterminated `A|B|` rather than `B`.
+    ///
+    /// [`NelfStr::len_cells`]: NelfStr::len_cells
+    pub fn last(&self) -> Option<&'a [u8]> {
+        self.iter().last()
+    }
+}
+
+impl<'a> From<&'a [u8]> for NelfStr<'a> {
+    fn from(string: &'a [u8]) -> Self {
+        NelfStr(string)
+    }
+}
+
+/// Checks that every cell of the encoded list is terminated by its closing
+/// run.
+///
+/// [`NelfIter`] accepts such lists too, treating the rest of the string as
+/// the contents of the last cell.
+///
+/// [`NelfIter`]: NelfIter
+pub fn validate(string: &[u8]) -> Result<(), NelfError> {
+    let mut iter = NelfIter::from_string(string);
+    let mut index = 0;
+
+    while let Some((_, terminated)) = iter.next_range() {
+        if !terminated {
+            return Err(NelfError::Unterminated { index });
+        }
+
+        index += 1;
+    }
+
+    match string.get(iter.index) {
+        Some(&ch) if IS_DELIM[ch as usize] => {
+            Err(NelfError::Unterminated { index })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the delimiter closing cells opened by the delimiter.
+fn closing(open: u8) -> u8 {
+    match open {
+        b'|' => b'|',
+        b'/' => b'\\',
+        b'\\' => b'/',
+        _ => unreachable!(),
+    }
+}
+
+/// Finds the first complete cell of a string arriving in pieces, keeping its
+/// progress between them, so that no byte is scanned twice.
+///
+/// The cells found are the same as the first cell [`NelfIter`] yields, as
+/// long as it is terminated.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct CellScanner {
+    pos: usize,
+    state: ScanState,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+enum ScanState {
+    #[default]
+    Comment,
+    Opening {
+        open: u8,
+        len: usize,
+    },
+    Content {
+        start: usize,
+        close: u8,
+        len: usize,
+        run: usize,
+    },
+}
+
+#[cfg(feature = "alloc")]
+impl CellScanner {
+    /// Scans the part of the string that hasn't been scanned yet, the string
+    /// having to start with everything scanned before.
+    ///
+    /// Returns the same as [`CellScanner::feed`].
+    pub(crate) fn scan(
+        &mut self,
+        string: &[u8],
+    ) -> Option<(Range<usize>, usize)> {
+        self.feed(&string[self.pos..])
+    }
+
+    /// Scans the piece of the string following the ones scanned before.
+    ///
+    /// Once the closing run of the cell is found, returns the range of its
+    /// contents and the length of the string it occupies, both counted from
+    /// the start of the first piece, and starts over, so that the rest of
+    /// the string has to be scanned again as a new one.
+    pub(crate) fn feed(
+        &mut self,
+        piece: &[u8],
+    ) -> Option<(Range<usize>, usize)> {
+        let mut index = 0;
+
+        while index < piece.len() {
+            match self.state {
+                ScanState::Comment => {
+                    let Some(offset) = find_delimiter(&piece[index..]) else {
+                        break;
+                    };
+
+                    index += offset;
+                    self.state = ScanState::Opening {
+                        open: piece[index],
+                        len: 0,
+                    };
+                }
+                ScanState::Opening { open, len } => {
+                    let count = piece[index..]
+                        .iter()
+                        .take_while(|&&ch| ch == open)
+                        .count();
+                    index += count;
+
+                    self.state = if index < piece.len() {
+                        ScanState::Content {
+                            start: self.pos + index,
+                            close: closing(open),
+                            len: len + count,
+                            run: 0,
+                        }
+                    } else {
+                        ScanState::Opening {
+                            open,
+                            len: len + count,
+                        }
+                    };
+                }
+                ScanState::Content {
+                    start,
+                    close,
+                    len,
+                    run,
+                } => {
+                    if run == 0 {
+                        let Some(offset) = find_byte(&piece[index..], close)
+                        else {
+                            break;
+                        };
+
+                        index += offset;
+                    }
+
+                    let count = piece[index..]
+                        .iter()
+                        .take(len - run)
+                        .take_while(|&&ch| ch == close)
+                        .count();
+                    index += count;
+
+                    if run + count == len {
+                        let end = self.pos + index;
+                        *self = CellScanner::default();
+                        return Some((start..end - len, end));
+                    }
+
+                    // The run is only continued by the next piece if this one
+                    // ends in the middle of it.
+                    let run = if index < piece.len() { 0 } else { run + count };
+                    self.state = ScanState::Content {
+                        start,
+                        close,
+                        len,
+                        run,
+                    };
+                }
+            }
+        }
+
+        self.pos += piece.len();
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+
+    use crate::NelfError;
+
+    #[cfg(feature = "alloc")]
+    use super::CellScanner;
+    use super::{validate, NelfIter, NelfStr};
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_1() {
+        assert_eq!(NelfIter::from_string(b"C|A|C").collect::<Vec<_>>(), [b"A"]);
+        assert_eq!(NelfIter::from_string(b"|A|").collect::<Vec<_>>(), [b"A"]);
+        assert_eq!(
+            NelfIter::from_string(b"C||A||C||B||C").collect::<Vec<_>>(),
+            [b"A", b"B"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_2() {
+        assert_eq!(
+            NelfIter::from_string(b"C/A\\C").collect::<Vec<_>>(),
+            [b"A"]
+        );
+        assert_eq!(
+            NelfIter::from_string(b"C\\A/C").collect::<Vec<_>>(),
+            [b"A"]
+        );
+        assert_eq!(NelfIter::from_string(b"/A\\").collect::<Vec<_>>(), [b"A"]);
+        assert_eq!(NelfIter::from_string(b"\\A/").collect::<Vec<_>>(), [b"A"]);
+        assert_eq!(
+            NelfIter::from_string(b"C//A\\\\C\\\\B//C").collect::<Vec<_>>(),
+            [b"A", b"B"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_3() {
+        assert_eq!(NelfIter::from_string(b"123").next(), None);
+        assert_eq!(
+            NelfIter::from_string(b"|ABC").collect::<Vec<_>>(),
+            [b"ABC"]
+        );
+        assert_eq!(
+            NelfIter::from_string(b"/ABC").collect::<Vec<_>>(),
+            [b"ABC"]
+        );
+        assert_eq!(
+            NelfIter::from_string(b"\\ABC").collect::<Vec<_>>(),
+            [b"ABC"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_4() {
+        assert_eq!(
+            NelfIter::from_string(b"||A|A||").collect::<Vec<_>>(),
+            [b"A|A"]
+        );
+        assert_eq!(
+            NelfIter::from_string(b"|A/\\A|").collect::<Vec<_>>(),
+            [b"A/\\A"]
+        );
+        assert_eq!(NelfIter::from_string(b"/|\\").collect::<Vec<_>>(), [b"|"]);
+        assert_eq!(NelfIter::from_string(b"\\|/").collect::<Vec<_>>(), [b"|"]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_all_delimiters() {
+        assert_eq!(NelfIter::from_string(b"||").next(), None);
+        assert_eq!(NelfIter::from_string(b"||||").next(), None);
+        assert_eq!(NelfIter::from_string(b"//\\\\").collect::<Vec<_>>(), [b""]);
+        assert_eq!(NelfIter::from_string(b"/\\").collect::<Vec<_>>(), [b""]);
+        assert_eq!(NelfIter::from_string(b"|A|||").collect::<Vec<_>>(), [b"A"]);
+
+        assert!(validate(b"||").is_err());
+        assert!(validate(b"||||").is_err());
+        assert_eq!(validate(b"//\\\\"), Ok(()));
+        assert_eq!(validate(b"/\\"), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_short_interior_runs() {
+        assert_eq!(
+            NelfIter::from_string(b"||a|b||").collect::<Vec<_>>(),
+            [b"a|b"]
+        );
+        assert_eq!(
+            NelfIter::from_string(b"|||a||b|||").collect::<Vec<_>>(),
+            [b"a||b"]
+        );
+        assert_eq!(
+            NelfIter::from_string(b"|||a|b||c|||").collect::<Vec<_>>(),
+            [b"a|b||c"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_interior_runs() {
+        let fixtures: [(&[u8], &[u8]); 5] = [
+            (b"||/\\|/||", b"/\\|/"),
+            (b"\\\\/|/\\//", b"/|/\\"),
+            (b"|||//||\\\\|||", b"//||\\\\"),
+            (b"//\\/\\\\", b"\\/"),
+            (b"\\\\\\//|\\///", b"//|\\"),
+        ];
+
+        for (cell, content) in fixtures {
+            assert_eq!(
+                NelfIter::from_string(cell).collect::<Vec<_>>(),
+                [content]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_back_to_back() {
+        let fixtures: [(&[u8], &[&[u8]]); 8] = [
+            (b"|A||B|", &[b"A", b"B"]),
+            (b"C||A||C||B||C", &[b"A", b"B"]),
+            (b"||A||||B||", &[b"A", b"B"]),
+            (b"|A|||B|", &[b"A", b"B|"]),
+            (b"|A|||||B|", &[b"A", b"B|"]),
+            (b"||A||||", &[b"A"]),
+            (b"|A|/\\|B|", &[b"A", b"", b"B"]),
+            (b"/\\\\/", &[b"", b""]),
+        ];
+
+        for (string, cells) in fixtures {
+            assert_eq!(
+                NelfIter::from_string(string).collect::<Vec<_>>(),
+                cells
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_long_runs() {
+        let run = vec![b'|'; 1 << 20];
+        let cell = [&run[..], b"A", &run].concat();
+        assert_eq!(NelfIter::from_string(&cell).collect::<Vec<_>>(), [b"A"]);
+        assert_eq!(NelfIter::from_string(&run).next(), None);
+
+        let unterminated = &cell[..cell.len() - 1];
+        assert_eq!(
+            NelfIter::from_string(unterminated).collect::<Vec<_>>(),
+            [&unterminated[run.len()..]]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_zero_copy() {
+        fn first(string: &[u8]) -> Option<&[u8]> {
+            NelfIter::from_string(string).next()
+        }
+
+        let source = b"C|A|/B\\C|/\\|/\\".to_vec();
+        let range = source.as_ptr_range();
+        let cells: Vec<_> = NelfIter::from_string(&source).collect();
+        assert_eq!(cells, [&b"A"[..], b"B", b"/\\", b""]);
+
+        for cell in cells {
+            assert!(range.contains(&cell.as_ptr()));
+            assert!(cell.as_ptr_range().end <= range.end);
+        }
+
+        let cell = first(&source).unwrap();
+        assert_eq!(cell.as_ptr(), source[2..].as_ptr());
+
+        let string = NelfStr::from(&source[..]);
+        assert_eq!(string.get(1).unwrap().as_ptr(), source[5..].as_ptr());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_size_hint() {
+        let mut iter = NelfIter::from_string(b"|A|/B\\|C");
+        assert_eq!(iter.size_hint(), (1, Some(4)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(2)));
+        assert_eq!(iter.collect::<Vec<_>>(), [b"B", b"C"]);
+        assert_eq!(NelfIter::from_string(b"").size_hint(), (0, Some(0)));
+        assert_eq!(NelfIter::from_string(b"C|A|").size_hint(), (0, Some(2)));
+        assert_eq!(NelfIter::from_string(b"|A||").size_hint(), (0, Some(2)));
+        assert_eq!(NelfIter::from_string(b"///").size_hint(), (0, Some(1)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_size_hint_bounds() {
+        let alphabet = [b'|', b'/', b'\\', b'A'];
+
+        for len in 0..=7 {
+            for mut code in 0..alphabet.len().pow(len) {
+                let string: Vec<u8> = (0..len)
+                    .map(|_| {
+                        let ch = alphabet[code % alphabet.len()];
+                        code /= alphabet.len();
+                        ch
+                    })
+                    .collect();
+                let mut iter = NelfIter::from_string(&string);
+
+                loop {
+                    let (lower, upper) = iter.size_hint();
+                    let count = iter.count();
+                    assert!(lower <= count, "{string:?}");
+                    assert!(count <= upper.unwrap(), "{string:?}");
+
+                    if iter.next().is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_iter_partial_runs() {
+        assert_eq!(
+            NelfIter::from_string(b"C|||A|B||C|||D/E\\").collect::<Vec<_>>(),
+            [&b"A|B||C"[..], b"E"]
+        );
+
+        let content = b"A||B|C".repeat(1000);
+        let string = [&b"|||"[..], &content, b"|||/C\\"].concat();
+        assert_eq!(
+            NelfIter::from_string(&string).collect::<Vec<_>>(),
+            [&content[..], b"C"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cell_scanner_pieces() {
+        let alphabet = [b'|', b'/', b'\\', b'A'];
+
+        for len in 0..=7 {
+            for mut code in 0..alphabet.len().pow(len) {
+                let string: Vec<u8> = (0..len)
+                    .map(|_| {
+                        let ch = alphabet[code % alphabet.len()];
+                        code /= alphabet.len();
+                        ch
+                    })
+                    .collect();
+                let mut iter = NelfIter::from_string(&string);
+                let expected = match iter.next_range() {
+                    Some((range, true)) => Some((range, iter.consumed())),
+                    _ => None,
+                };
+
+                let mut scanner = CellScanner::default();
+                let found =
+                    string.chunks(1).find_map(|piece| scanner.feed(piece));
+                assert_eq!(found, expected, "{string:?}");
+
+                for split in 0..=string.len() {
+                    let (a, b) = string.split_at(split);
+                    let mut scanner = CellScanner::default();
+                    let found = scanner.feed(a).or_else(|| scanner.feed(b));
+                    assert_eq!(found, expected, "{string:?} at {split}");
+                    let mut scanner = CellScanner::default();
+                    let found = scanner
+                        .scan(a)
+                        .or_else(|| scanner.scan(&string))
+                        .or_else(|| scanner.scan(&string));
+                    assert_eq!(found, expected, "{string:?} at {split}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_1() {
+        assert_eq!(validate(b""), Ok(()));
+        assert_eq!(validate(b"C||A||C/B\\C"), Ok(()));
+        assert_eq!(validate(b"|A"), Err(NelfError::Unterminated { index: 0 }));
+        assert_eq!(
+            validate(b"|A|/B"),
+            Err(NelfError::Unterminated { index: 1 })
+        );
+        assert_eq!(
+            validate(b"|A|C||"),
+            Err(NelfError::Unterminated { index: 1 })
+        );
+    }
+
+    #[test]
+    fn with_termination_1() {
+        let mut iter = NelfIter::from_string(b"|A|").with_termination();
+        assert_eq!(iter.next(), Some((&b"A"[..], true)));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = NelfIter::from_string(b"|A").with_termination();
+        assert_eq!(iter.next(), Some((&b"A"[..], false)));
+        assert_eq!(iter.next(), None);
+
+        // Without the adaptor both lists yield the same cell.
+        assert!(NelfIter::from_string(b"|A|").eq(NelfIter::from_string(b"|A")));
+
+        let mut iter = NelfIter::from_string(b"|A|/B|C").with_termination();
+        assert_eq!(iter.next(), Some((&b"A"[..], true)));
+        assert_eq!(iter.next(), Some((&b"B|C"[..], false)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nelf_str_1() {
+        let string = NelfStr::from(&b"|A|/|\\ \\C/"[..]);
+        assert_eq!(string.iter().collect::<Vec<_>>(), [&b"A"[..], b"|", b"C"]);
+        assert_eq!(string.len_cells(), 3);
+        assert_eq!(string.get(1), Some(&b"|"[..]));
+        assert_eq!(string.get(3), None);
+        assert_eq!(NelfStr::default().len_cells(), 0);
+    }
+
+    #[test]
+    fn nelf_str_first_last() {
+        let string = NelfStr::from(&b"|A||B||C|"[..]);
+        assert_eq!(string.first(), Some(&b"A"[..]));
+        assert_eq!(string.last(), Some(&b"C"[..]));
+        assert_eq!(NelfStr::from(&b"/A|B|"[..]).last(), Some(&b"A|B|"[..]));
+        assert_eq!(NelfStr::default().first(), None);
+        assert_eq!(NelfStr::default().last(), None);
+    }
+}
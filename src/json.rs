@@ -0,0 +1,321 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+use core::str;
+
+use crate::text::str_cells;
+use crate::{encode_cell_append, NelfError};
+#[cfg(feature = "serde_json")]
+use crate::{NelfIter, ToNelf};
+
+/// Error returned when the input is not a valid JSON array of strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JsonError {
+    offset: usize,
+}
+
+impl JsonError {
+    /// Returns the byte offset in the input at which the error was found.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid JSON array of strings at byte {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonError {}
+
+/// Converts the list into a JSON array of strings.
+///
+/// Returns an error if any of the cells is not valid UTF-8.
+pub fn to_json_array(string: &[u8]) -> Result<String, NelfError> {
+    let mut result = String::from("[");
+
+    for (index, cell) in str_cells(string).enumerate() {
+        let cell = cell?;
+
+        if index != 0 {
+            result.push(',');
+        }
+
+        result.push('"');
+
+        for ch in cell.chars() {
+            match ch {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\t' => result.push_str("\\t"),
+                '\u{8}' => result.push_str("\\b"),
+                '\u{c}' => result.push_str("\\f"),
+                ..='\u{1f}' => write!(result, "\\u{:04x}", ch as u32).unwrap(),
+                _ => result.push(ch),
+            }
+        }
+
+        result.push('"');
+    }
+
+    result.push(']');
+
+    Ok(result)
+}
+
+/// Converts a JSON array of strings into a NELF list.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+pub fn from_json_array(json: &str) -> Result<Vec<u8>, JsonError> {
+    let mut parser = Parser {
+        json: json.as_bytes(),
+        offset: 0,
+    };
+    let mut result = Vec::new();
+
+    parser.whitespace();
+    parser.expect(b'[')?;
+    parser.whitespace();
+
+    if parser.peek() == Some(b']') {
+        parser.offset += 1;
+    } else {
+        loop {
+            parser.whitespace();
+            encode_cell_append(&parser.string()?, &mut result);
+            parser.whitespace();
+
+            match parser.next() {
+                Some(b',') => (),
+                Some(b']') => break,
+                _ => return Err(parser.error()),
+            }
+        }
+    }
+
+    parser.whitespace();
+
+    match parser.peek() {
+        Some(_) => Err(JsonError {
+            offset: parser.offset,
+        }),
+        None => Ok(result),
+    }
+}
+
+/// Converts the list into a tree of JSON arrays, recursively parsing cells
+/// that contain nested lists.
+///
+/// Cells are parsed up to `max_depth` levels below the top-level list. A cell
+/// is considered a nested list if it is not empty and is exactly the encoding
+/// of its own cells. Other cells become strings, with invalid UTF-8 replaced.
+#[cfg(feature = "serde_json")]
+pub fn to_json_tree(string: &[u8], max_depth: usize) -> serde_json::Value {
+    serde_json::Value::Array(
+        NelfIter::from_string(string)
+            .map(|cell| match max_depth.checked_sub(1) {
+                Some(depth)
+                    if !cell.is_empty()
+                        && NelfIter::from_string(cell).to_nelf() == cell =>
+                {
+                    to_json_tree(cell, depth)
+                }
+                _ => String::from_utf8_lossy(cell).into(),
+            })
+            .collect(),
+    )
+}
+
+struct Parser<'a> {
+    json: &'a [u8],
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self) -> JsonError {
+        JsonError {
+            offset: self.offset.saturating_sub(1),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.json.get(self.offset).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let ch = self.peek();
+        self.offset += 1;
+        ch
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), JsonError> {
+        match self.next() {
+            Some(ch) if ch == expected => Ok(()),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.offset += 1;
+        }
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>, JsonError> {
+        let mut result = Vec::new();
+
+        self.expect(b'"')?;
+
+        loop {
+            match self.next().ok_or_else(|| self.error())? {
+                b'"' => return Ok(result),
+                b'\\' => {
+                    let ch = match self.next().ok_or_else(|| self.error())? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode()?,
+                        _ => return Err(self.error()),
+                    };
+
+                    let mut buf = [0; 4];
+                    result
+                        .extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                }
+                ..=0x1f => return Err(self.error()),
+                ch => result.push(ch),
+            }
+        }
+    }
+
+    fn unicode(&mut self) -> Result<char, JsonError> {
+        let high = self.hex()?;
+
+        if !(0xd800..0xdc00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error());
+        }
+
+        self.expect(b'\\')?;
+        self.expect(b'u')?;
+
+        let low = self.hex()?;
+
+        if !(0xdc00..0xe000).contains(&low) {
+            return Err(self.error());
+        }
+
+        char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+            .ok_or_else(|| self.error())
+    }
+
+    fn hex(&mut self) -> Result<u32, JsonError> {
+        let digits = self
+            .json
+            .get(self.offset..self.offset + 4)
+            .and_then(|digits| str::from_utf8(digits).ok())
+            .filter(|digits| digits.bytes().all(|ch| ch.is_ascii_hexdigit()))
+            .ok_or(JsonError {
+                offset: self.offset,
+            })?;
+
+        self.offset += 4;
+
+        Ok(u32::from_str_radix(digits, 16).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{NelfError, NelfIter, ToNelf};
+
+    use super::{from_json_array, to_json_array};
+
+    #[test]
+    fn to_json_array_1() {
+        assert_eq!(to_json_array(b"|A||B|").unwrap(), r#"["A","B"]"#);
+        assert_eq!(to_json_array(b"").unwrap(), "[]");
+        assert_eq!(
+            to_json_array(&[&b"\"\\/|"[..], b"\n\x01"].to_nelf()).unwrap(),
+            r#"["\"\\/|","\n\u0001"]"#
+        );
+    }
+
+    #[test]
+    fn to_json_array_2() {
+        assert_eq!(
+            to_json_array(b"|A||\xff|"),
+            Err(NelfError::InvalidUtf8 { index: 1 })
+        );
+    }
+
+    #[test]
+    fn from_json_array_1() {
+        let nelf = from_json_array(r#"["A","B"]"#).unwrap();
+        assert_eq!(
+            NelfIter::from_string(&nelf).collect::<Vec<_>>(),
+            [b"A", b"B"]
+        );
+        assert_eq!(from_json_array(" [ ] ").unwrap(), b"");
+    }
+
+    #[test]
+    fn from_json_array_2() {
+        let nelf =
+            from_json_array(r#" [ "\"\\\/|" , "\n\u0001é😀" ] "#).unwrap();
+        assert_eq!(
+            NelfIter::from_string(&nelf).collect::<Vec<_>>(),
+            [&b"\"\\/|"[..], "\n\u{1}\u{e9}\u{1f600}".as_bytes()]
+        );
+    }
+
+    #[test]
+    fn from_json_array_3() {
+        assert_eq!(from_json_array(r#"["A",]"#).unwrap_err().offset(), 5);
+        assert_eq!(from_json_array(r#"["A"] 1"#).unwrap_err().offset(), 6);
+        assert!(from_json_array(r#"["A""#).is_err());
+        assert!(from_json_array(r#"[1]"#).is_err());
+        assert!(from_json_array(r#"["\x"]"#).is_err());
+        assert!(from_json_array(r#"["\ud83d"]"#).is_err());
+        assert!(from_json_array("").is_err());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn to_json_tree_1() {
+        use serde_json::json;
+
+        use super::to_json_tree;
+
+        let item = b"ABCD/|\\";
+        let inner = [item].to_nelf();
+        let middle = [&inner].to_nelf();
+        let nelf = [&middle[..], b"A"].to_nelf();
+
+        assert_eq!(to_json_tree(&nelf, 2), json!([[["ABCD/|\\"]], "A"]));
+        assert_eq!(to_json_tree(&nelf, 8), json!([[["ABCD/|\\"]], "A"]));
+        assert_eq!(
+            to_json_tree(&nelf, 1),
+            json!([[String::from_utf8(inner).unwrap()], "A"])
+        );
+        assert_eq!(
+            to_json_tree(&nelf, 0),
+            json!([String::from_utf8(middle).unwrap(), "A"])
+        );
+    }
+}
@@ -16,335 +16,249 @@
 //! traits:
 //!
 //! * [`NelfIter`]
-//! * [`ToCell`]
-//! * [`ToNelf`]
+#![cfg_attr(feature = "std", doc = "* [`NelfReader`]")]
+#![cfg_attr(feature = "alloc", doc = "* [`IncrementalParser`]")]
+#![cfg_attr(feature = "alloc", doc = "* [`Nelf`]")]
+//! * [`validate`]
+#![cfg_attr(feature = "alloc", doc = "* [`ToCell`]")]
+#![cfg_attr(feature = "alloc", doc = "* [`ToNelf`]")]
+#![cfg_attr(feature = "alloc", doc = "* [`Encoder`]")]
+#![cfg_attr(feature = "alloc", doc = "* [`NelfBuilder`]")]
 //!
 //! [`NelfIter`]: NelfIter
-//! [`ToCell`]: ToCell
-//! [`ToNelf`]: ToNelf
-
+//! [`validate`]: validate
+#![cfg_attr(feature = "std", doc = "[`NelfReader`]: NelfReader")]
+#![cfg_attr(
+    feature = "alloc",
+    doc = "
+[`IncrementalParser`]: IncrementalParser
+[`Nelf`]: Nelf
+[`ToCell`]: ToCell
+[`ToNelf`]: ToNelf
+[`Encoder`]: Encoder
+[`NelfBuilder`]: NelfBuilder
+"
+)]
+//!
+//! ## Features
+//!
+//! Without the `std` feature, which is enabled by default, the crate is
+//! `no_std`. The feature provides `NelfReader`, `send_cell`, `recv_cell` and
+//! `collect_pairs`.
+//!
+//! The `alloc` feature, enabled by `std`, provides everything that allocates,
+//! such as most of encoding. Without it only the items that parse borrowed
+//! lists without allocating are available, such as `NelfIter`, `NelfStr`,
+//! `validate`, `max_runs`, `fold_cells`, `contains_cell` and `position_of`,
+//! along with `encoded_len`, `checked_encoded_len` and `encode_list_buf`,
+//! which encode into buffers provided by the caller.
+#![cfg_attr(
+    not(feature = "std"),
+    doc = "
+This build doesn't have the `std` feature enabled, so the types based on
+`std::io` are not available, while parsing is:
+
+```compile_fail
+use nelf::NelfReader;
+```
+
+```
+use nelf::NelfIter;
+
+let string = b\"|A|/B\\\\\";
+assert_eq!(NelfIter::from_string(string).count(), 2);
+```
+"
+)]
+//!
+//! Integrations with other crates are available behind optional features,
+//! most of which enable `std` as well:
+//!
+//! * `arbitrary`: `Arbitrary` for `Nelf`, generating valid encoded lists for
+//!   fuzzing.
+//! * `arrayvec`: `to_cell_arrayvec`, encoding cells into a stack-backed
+//!   `arrayvec::ArrayVec`.
+//! * `bumpalo`: `decode_into_arena`, copying decoded cells into a
+//!   `bumpalo::Bump` arena.
+//! * `bytes`: decoding cells from a `bytes::Buf` with `decode_buf` and
+//!   encoding them into a `bytes::BufMut` with `encode_buf`.
+//! * `ffi`: C functions iterating over the cells of a list through an opaque
+//!   `NelfIterHandle` or parsing them incrementally through
+//!   `NelfIncrementalHandle`, and encoding lists with `nelf_encode`.
+//! * `futures`: `CellStreamExt`, adaptors for streams of cells.
+//! * `heapless`: `to_cell_heapless`, encoding cells into a fixed-capacity
+//!   `heapless::Vec`.
+//! * `memchr`: faster search for delimiters using `memchr`.
+//! * `proptest`: `strategy::nelf_strategy`, a proptest strategy generating
+//!   lists of cells along with their encoding.
+//! * `rayon`: `par_decode`, dereferencing the cells of a list in parallel.
+//! * `serde`: `Serialize` and `Deserialize` for `Nelf` and `NelfList`, and
+//!   `to_nelf_serde` and `from_nelf_serde` converting sequences of
+//!   primitives to and from NELF lists. `serde_bytes_cells` serializes lists
+//!   of cells as byte strings.
+//! * `serde_json`: `to_json_tree`, rendering nested lists as JSON arrays.
+//! * `simd`: vectorized search for delimiters using `wide`, taking
+//!   precedence over `memchr`.
+//! * `smallvec`: `decode_smallvec`, collecting short lists of cells without
+//!   allocating.
+//! * `tokio-util`: `NelfCodec`, a `tokio_util::codec` framing a stream as a
+//!   list of cells.
+//! * `toml`: `from_toml_array` and `to_toml_array` converting TOML arrays of
+//!   strings to and from NELF lists.
+//! * `wasm`: `wasm::encode`, `wasm::decode` and `wasm::decode_strings`,
+//!   bindings for JavaScript through `wasm-bindgen`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
-use private::{ToCellSealed, ToNelfSealed};
-
-/// Iterator of cells contained in the encoded list.
-///
-/// Borrows the source and iterates of string slices borrowing from that source.
-#[derive(Clone, Copy)]
-pub struct NelfIter<'a> {
-    string: &'a [u8],
-    index: usize,
-}
-
-impl<'a> NelfIter<'a> {
-    /// Construct the iterator borrowing from the encoded list.
-    pub fn from_string(string: &'a [u8]) -> Self {
-        NelfIter { string, index: 0 }
-    }
-}
-
-impl<'a> Iterator for NelfIter<'a> {
-    type Item = &'a [u8];
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let lch;
-        (self.index, lch) = self.string[self.index..]
-            .iter()
-            .enumerate()
-            .find(|&(_, &ch)| matches!(ch, b'|' | b'/' | b'\\'))
-            .map(|(index, &ch)| (self.index + index, ch))?;
-
-        let len = self.string[self.index..]
-            .iter()
-            .enumerate()
-            .find(|&(_, &ch)| ch != lch)
-            .map(|(index, _)| index)?;
-
-        let start = self.index + len;
-
-        self.index = start;
-
-        let rch = match lch {
-            b'|' => b'|',
-            b'/' => b'\\',
-            b'\\' => b'/',
-            _ => unreachable!(),
-        };
-
-        let mut count = 0;
-
-        while self.index < self.string.len() {
-            if count == len {
-                break;
-            }
-
-            if self.string[self.index] == rch {
-                count += 1;
-            } else {
-                count = 0;
-            }
-
-            self.index += 1;
-        }
-
-        Some(if count == len {
-            &self.string[start..self.index - len]
-        } else {
-            &self.string[start..]
-        })
-    }
-}
-
-/// Trait used to encode strings as cells in a NELF list.
-///
-/// Already implemented for the most commonly used types, sealed.
-pub trait ToCell: ToCellSealed {
-    /// Encodes the value as a NELF cell.
-    fn to_cell(self) -> Vec<u8>;
-}
-
-impl ToCellSealed for &[u8] {}
-
-impl ToCell for &[u8] {
-    fn to_cell(self) -> Vec<u8> {
-        let mut result = Vec::new();
-
-        if !self.is_empty() {
-            let mut pipe = true;
-            let mut forward = true;
-            let mut back = true;
-
-            match self.first().unwrap() {
-                b'|' => pipe = false,
-                b'/' => forward = false,
-                b'\\' => back = false,
-                _ => (),
-            }
-
-            match self.last().unwrap() {
-                b'|' => pipe = false,
-                b'/' => back = false,
-                b'\\' => forward = false,
-                _ => (),
-            }
-
-            let mut pipe_max = usize::MAX;
-            let mut forward_max = usize::MAX;
-            let mut back_max = usize::MAX;
-
-            if pipe {
-                pipe_max = self
-                    .iter()
-                    .scan(0, |state, &x| {
-                        if x == b'|' {
-                            *state += 1;
-                        } else {
-                            *state = 0;
-                        }
-
-                        Some(*state)
-                    })
-                    .max()
-                    .unwrap()
-                    + 1;
-            }
-
-            if forward {
-                forward_max = self
-                    .iter()
-                    .scan(0, |state, &x| {
-                        if x == b'\\' {
-                            *state += 1;
-                        } else {
-                            *state = 0;
-                        }
-
-                        Some(*state)
-                    })
-                    .max()
-                    .unwrap()
-                    + 1;
-            }
-
-            if back {
-                back_max = self
-                    .iter()
-                    .scan(0, |state, &x| {
-                        if x == b'/' {
-                            *state += 1;
-                        } else {
-                            *state = 0;
-                        }
-
-                        Some(*state)
-                    })
-                    .max()
-                    .unwrap()
-                    + 1;
-            }
-
-            let min = pipe_max.min(forward_max).min(back_max);
-
-            if pipe_max == min {
-                result.resize(result.len() + min, b'|');
-                result.extend_from_slice(self);
-                result.resize(result.len() + min, b'|');
-            } else if forward_max == min {
-                result.resize(result.len() + min, b'/');
-                result.extend_from_slice(self);
-                result.resize(result.len() + min, b'\\');
-            } else {
-                result.resize(result.len() + min, b'\\');
-                result.extend_from_slice(self);
-                result.resize(result.len() + min, b'/');
-            }
-        } else {
-            result.extend_from_slice(b"/\\");
-        }
-
-        result
-    }
-}
-
-impl<const N: usize> ToCellSealed for &[u8; N] {}
-
-impl<const N: usize> ToCell for &[u8; N] {
-    fn to_cell(self) -> Vec<u8> {
-        self.as_slice().to_cell()
-    }
-}
-
-impl<const N: usize> ToCellSealed for [u8; N] {}
-
-impl<const N: usize> ToCell for [u8; N] {
-    fn to_cell(self) -> Vec<u8> {
-        self.as_slice().to_cell()
-    }
-}
-
-impl ToCellSealed for &Vec<u8> {}
-
-impl ToCell for &Vec<u8> {
-    fn to_cell(self) -> Vec<u8> {
-        self.as_slice().to_cell()
-    }
-}
-
-impl ToCellSealed for Vec<u8> {}
-
-impl ToCell for Vec<u8> {
-    fn to_cell(self) -> Vec<u8> {
-        self.as_slice().to_cell()
-    }
-}
-
-/// Trait used to convert containers of strings to NELF strings.
-///
-/// Already implemented for all iterables of byte slices. Sealed.
-pub trait ToNelf: ToNelfSealed {
-    /// Converts the list of strings into a NELF string.
-    fn to_nelf(self) -> Vec<u8>;
-}
-
-impl<T: IntoIterator<Item = V>, V: ToCell> ToNelfSealed for T {}
-
-impl<T: IntoIterator<Item = V>, V: ToCell> ToNelf for T {
-    fn to_nelf(self) -> Vec<u8> {
-        let mut result = Vec::new();
-
-        for string in self.into_iter() {
-            result.append(&mut string.to_cell());
-        }
-
-        result
-    }
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "arrayvec")]
+pub use crate::arrayvec::to_cell_arrayvec;
+#[cfg(feature = "heapless")]
+pub use crate::heapless::to_cell_heapless;
+#[cfg(feature = "serde")]
+pub use crate::serde::{
+    from_nelf_serde, serde_bytes_cells, to_nelf_serde, SerdeError,
+};
+#[cfg(feature = "smallvec")]
+pub use crate::smallvec::decode_smallvec;
+#[cfg(feature = "toml")]
+pub use crate::toml::{from_toml_array, to_toml_array, TomlError};
+#[cfg(feature = "bumpalo")]
+pub use arena::decode_into_arena;
+#[cfg(feature = "bytes")]
+pub use buf::{decode_buf, encode_buf, BufError};
+#[cfg(feature = "alloc")]
+pub use builder::NelfBuilder;
+#[cfg(feature = "tokio-util")]
+pub use codec::NelfCodec;
+#[cfg(feature = "alloc")]
+pub use csv::{csv_row_to_nelf, nelf_to_csv_row, CsvError};
+#[cfg(feature = "alloc")]
+pub use encode::{
+    encode_cell_append, encode_cell_ascii_fast, encode_list,
+    try_encode_cell_append, ToCell, ToNelf,
+};
+#[cfg(feature = "alloc")]
+pub use encoder::Encoder;
+pub use error::NelfError;
+#[cfg(feature = "ffi")]
+pub use ffi::{
+    nelf_encode, nelf_free, nelf_incremental_feed, nelf_incremental_free,
+    nelf_incremental_new, nelf_incremental_next, nelf_iter_free, nelf_iter_new,
+    nelf_iter_next, NelfIncrementalHandle, NelfIterHandle,
+};
+#[cfg(feature = "alloc")]
+pub use incremental::{IncrementalParser, ParserState};
+#[cfg(feature = "std")]
+pub use io::{recv_cell, send_cell, NelfReader};
+pub use iter::{validate, NelfIter, NelfStr, WithTermination};
+#[cfg(feature = "serde_json")]
+pub use json::to_json_tree;
+#[cfg(feature = "alloc")]
+pub use json::{from_json_array, to_json_array, JsonError};
+#[cfg(feature = "alloc")]
+pub use list::{Nelf, NelfIntoIter, NelfList, NelfListRef};
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use macros::__macros;
+#[cfg(feature = "std")]
+pub use ops::collect_pairs;
+pub use ops::{contains_cell, fold_cells, position_of, split_at};
+#[cfg(feature = "alloc")]
+pub use ops::{filter_cells, map_cells, reverse_cells};
+#[cfg(feature = "rayon")]
+pub use parallel::par_decode;
+#[cfg(feature = "futures")]
+pub use stream::{CellStreamExt, MapCells};
+#[cfg(feature = "alloc")]
+pub use text::{
+    decode_to_strings, encode_from_strings, from_display_lines,
+    to_display_lines,
+};
+pub use wrap::{checked_encoded_len, encode_list_buf, encoded_len};
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "bumpalo")]
+mod arena;
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "tokio-util")]
+mod codec;
+#[cfg(feature = "alloc")]
+mod csv;
+#[cfg(feature = "alloc")]
+mod encode;
+#[cfg(feature = "alloc")]
+mod encoder;
+mod error;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "alloc")]
+mod incremental;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+#[cfg(feature = "alloc")]
+mod json;
+#[cfg(feature = "alloc")]
+mod list;
+#[cfg(feature = "alloc")]
+mod macros;
+mod ops;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod scan;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod text;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod wrap;
+
+/// Returns the lengths of the longest runs of `|`, `/` and `\\` in the
+/// string, in that order.
+pub fn max_runs(string: &[u8]) -> [usize; 3] {
+    scan::max_runs(string)
 }
 
 mod private {
-    pub trait ToCellSealed {}
+    #[cfg(feature = "futures")]
+    pub trait CellStreamExtSealed {}
+    #[cfg(feature = "alloc")]
+    pub trait ToCellSealed {
+        fn content(&self) -> &[u8];
+    }
+    #[cfg(feature = "alloc")]
     pub trait ToNelfSealed {}
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ToCell;
-
-    use super::NelfIter;
-
-    #[test]
-    fn nelf_iter_1() {
-        assert_eq!(NelfIter::from_string(b"C|A|C").collect::<Vec<_>>(), [b"A"]);
-        assert_eq!(NelfIter::from_string(b"|A|").collect::<Vec<_>>(), [b"A"]);
-        assert_eq!(
-            NelfIter::from_string(b"C||A||C||B||C").collect::<Vec<_>>(),
-            [b"A", b"B"]
-        );
-    }
-
-    #[test]
-    fn nelf_iter_2() {
-        assert_eq!(
-            NelfIter::from_string(b"C/A\\C").collect::<Vec<_>>(),
-            [b"A"]
-        );
-        assert_eq!(
-            NelfIter::from_string(b"C\\A/C").collect::<Vec<_>>(),
-            [b"A"]
-        );
-        assert_eq!(NelfIter::from_string(b"/A\\").collect::<Vec<_>>(), [b"A"]);
-        assert_eq!(NelfIter::from_string(b"\\A/").collect::<Vec<_>>(), [b"A"]);
-        assert_eq!(
-            NelfIter::from_string(b"C//A\\\\C\\\\B//C").collect::<Vec<_>>(),
-            [b"A", b"B"]
-        );
-    }
-
-    #[test]
-    fn nelf_iter_3() {
-        assert_eq!(NelfIter::from_string(b"123").next(), None);
-        assert_eq!(
-            NelfIter::from_string(b"|ABC").collect::<Vec<_>>(),
-            [b"ABC"]
-        );
-        assert_eq!(
-            NelfIter::from_string(b"/ABC").collect::<Vec<_>>(),
-            [b"ABC"]
-        );
-        assert_eq!(
-            NelfIter::from_string(b"\\ABC").collect::<Vec<_>>(),
-            [b"ABC"]
-        );
-    }
-
-    #[test]
-    fn nelf_iter_4() {
-        assert_eq!(
-            NelfIter::from_string(b"||A|A||").collect::<Vec<_>>(),
-            [b"A|A"]
-        );
-        assert_eq!(
-            NelfIter::from_string(b"|A/\\A|").collect::<Vec<_>>(),
-            [b"A/\\A"]
-        );
-        assert_eq!(NelfIter::from_string(b"/|\\").collect::<Vec<_>>(), [b"|"]);
-        assert_eq!(NelfIter::from_string(b"\\|/").collect::<Vec<_>>(), [b"|"]);
-    }
-
-    #[test]
-    fn nelf_cell_1() {
-        assert_eq!(b"|".to_cell(), b"/|\\");
-        assert_eq!(b"/".to_cell(), b"|/|");
-        assert_eq!(b"\\".to_cell(), b"|\\|");
-        assert_eq!(b"||".to_cell(), b"/||\\");
-        assert_eq!(b"//".to_cell(), b"|//|");
-        assert_eq!(b"\\\\".to_cell(), b"|\\\\|");
-    }
+    use super::max_runs;
 
     #[test]
-    fn nelf_cell_2() {
-        assert_eq!(b"/|".to_cell(), b"\\\\/|//");
-        assert_eq!(b"\\|".to_cell(), b"//\\|\\\\");
-        assert_eq!(b"|/\\|".to_cell(), b"//|/\\|\\\\");
-        assert_eq!(b"/|/".to_cell(), b"||/|/||");
+    fn max_runs_1() {
+        assert_eq!(max_runs(b""), [0, 0, 0]);
+        assert_eq!(max_runs(b"||//\\\\\\"), [2, 2, 3]);
+        assert_eq!(max_runs(b"|A||A/|/"), [2, 1, 0]);
     }
 }
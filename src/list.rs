@@ -0,0 +1,846 @@
+use alloc::vec::Vec;
+use core::ascii;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, Index};
+
+use crate::encode::encode_cell;
+use crate::scan::find_delimiter;
+use crate::{filter_cells, validate, NelfError, NelfIter, ToCell};
+
+/// Owned encoded list.
+///
+/// Distinguishes an encoded list from arbitrary bytes. Unlike [`NelfIter`],
+/// it doesn't borrow anything, so it can be sent and shared across threads.
+///
+/// Dereferences to the encoded bytes, so it can be passed where raw bytes are
+/// expected. Its own methods and indexing work on cells instead, so slice
+/// methods like `len` count bytes while [`Nelf::len_cells`] counts cells.
+///
+/// The number of cells is kept along with the encoded list as long as the
+/// list is valid, see [`validate`], so [`Nelf::len_cells`] doesn't parse the
+/// list and [`Nelf::iter`] knows its exact size, letting
+/// [`Iterator::collect`] allocate the collection once.
+///
+/// [`Nelf::len_cells`]: Nelf::len_cells
+/// [`Nelf::iter`]: Nelf::iter
+/// [`Iterator::collect`]: Iterator::collect
+/// [`validate`]: crate::validate
+/// [`NelfIter`]: NelfIter
+#[derive(Clone)]
+pub struct Nelf {
+    string: Vec<u8>,
+    // Number of cells, if the list is known to be valid.
+    len: Option<usize>,
+}
+
+impl Nelf {
+    /// Construct an empty list with space reserved for `bytes` bytes of
+    /// encoded cells.
+    pub fn with_capacity(bytes: usize) -> Self {
+        Nelf {
+            string: Vec::with_capacity(bytes),
+            len: Some(0),
+        }
+    }
+
+    /// Construct the list from its encoded bytes, counting its cells if it
+    /// is valid.
+    #[cfg(any(feature = "serde", test))]
+    pub(crate) fn from_bytes(string: Vec<u8>) -> Self {
+        let len = count_valid(&string);
+        Nelf { string, len }
+    }
+
+    /// Construct the list by encoding the cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the list overflows `usize`, like
+    /// [`ToNelf::to_nelf`].
+    ///
+    /// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+    pub fn from_cells<T>(cells: T) -> Self
+    where
+        T: IntoIterator,
+        T::Item: ToCell,
+    {
+        let mut nelf = Nelf::default();
+
+        for cell in cells {
+            nelf.push_cell(cell);
+        }
+
+        nelf
+    }
+
+    /// Encodes the content as a cell at the end of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the cell overflows `usize`, like
+    /// [`ToCell::to_cell`].
+    ///
+    /// [`ToCell::to_cell`]: crate::ToCell::to_cell
+    pub fn push_cell(&mut self, cell: impl ToCell) {
+        encode_cell(cell.content(), &mut self.string);
+
+        if let Some(len) = &mut self.len {
+            *len += 1;
+        }
+    }
+
+    /// Removes all the cells, keeping the allocated space.
+    pub fn clear(&mut self) {
+        self.string.clear();
+        self.len = Some(0);
+    }
+
+    /// Keeps only the first `n` cells, removing the rest of the list.
+    ///
+    /// If a cell follows the `n`th one, the list is cut right after the
+    /// closing run of the `n`th cell, which leaves a valid list. Otherwise
+    /// nothing is done, so any comments after the last cell are kept, but so
+    /// are an unterminated last cell or an opening run at the end of the
+    /// list, which [`validate`] rejects.
+    ///
+    /// [`validate`]: crate::validate
+    pub fn truncate_cells(&mut self, n: usize) {
+        let Some(len) = self.boundary(n) else {
+            return;
+        };
+
+        if NelfIter::from_string(&self.string[len..]).next().is_some() {
+            self.string.truncate(len);
+            self.len = Some(n);
+        }
+    }
+
+    /// Encodes the content as a cell, inserting it so that it becomes the
+    /// cell at the index.
+    ///
+    /// The list is parsed up to the index to find where to insert the cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of cells, or if the
+    /// length of the cell overflows `usize`, like [`ToCell::to_cell`].
+    ///
+    /// [`ToCell::to_cell`]: crate::ToCell::to_cell
+    pub fn insert_cell(&mut self, index: usize, cell: impl ToCell) {
+        let Some(offset) = self.boundary(index) else {
+            panic!(
+                "insertion index (is {index}) should be <= len (is {})",
+                self.len_cells(),
+            );
+        };
+
+        let mut encoded = Vec::new();
+        encode_cell(cell.content(), &mut encoded);
+        self.string.splice(offset..offset, encoded);
+
+        if let Some(len) = &mut self.len {
+            *len += 1;
+        }
+    }
+
+    /// Removes the cell at the index, returning its contents, or `None` if
+    /// the list is shorter.
+    ///
+    /// Only the bytes of the cell itself are removed, along with its runs,
+    /// while the comments around it are kept.
+    pub fn remove_cell(&mut self, index: usize) -> Option<Vec<u8>> {
+        let offset = self.boundary(index)?;
+        let string = &self.string[offset..];
+        let mut iter = NelfIter::from_string(string);
+        let (range, _) = iter.next_range()?;
+        let cell = string[range].to_vec();
+
+        let start = offset + find_delimiter(string)?;
+        self.string.drain(start..offset + iter.consumed());
+
+        if let Some(len) = &mut self.len {
+            *len -= 1;
+        }
+
+        Some(cell)
+    }
+
+    /// Appends the cells of the other list, without decoding them.
+    ///
+    /// Cells are self-delimiting, so the encoded lists are simply joined. The
+    /// last cell of this list has to be terminated, which is always the case
+    /// for lists built by encoding cells.
+    pub fn concat(&mut self, other: &Nelf) {
+        self.string.extend_from_slice(&other.string);
+        self.len = self.len.zip(other.len).map(|(a, b)| a + b);
+    }
+
+    /// Appends the cells of the encoded list, without decoding them.
+    ///
+    /// The list is assumed to be valid, see [`validate`], and is appended as
+    /// is, like with [`Nelf::concat`]. Otherwise its unterminated last cell
+    /// would absorb the cells appended after it.
+    ///
+    /// [`validate`]: crate::validate
+    /// [`Nelf::concat`]: Nelf::concat
+    pub fn extend_from_nelf(&mut self, other: &[u8]) {
+        self.string.extend_from_slice(other);
+        self.len = self.len.zip(count_valid(other)).map(|(a, b)| a + b);
+    }
+
+    /// Joins the lists into a new one holding the cells of `a` followed by
+    /// those of `b`, like [`Nelf::concat`].
+    ///
+    /// [`Nelf::concat`]: Nelf::concat
+    pub fn merge(a: &Nelf, b: &Nelf) -> Nelf {
+        let mut result = Nelf::with_capacity(a.string.len() + b.string.len());
+        result.concat(a);
+        result.concat(b);
+        result
+    }
+
+    /// Keeps only the cells for which the predicate returns `true`.
+    ///
+    /// The list is rebuilt by encoding the kept cells, like with
+    /// [`filter_cells`].
+    ///
+    /// [`filter_cells`]: crate::filter_cells
+    pub fn retain<F: FnMut(&[u8]) -> bool>(&mut self, mut f: F) {
+        let mut len = 0;
+        self.string = filter_cells(&self.string, |cell| {
+            let keep = f(cell);
+            len += usize::from(keep);
+            keep
+        });
+        self.len = Some(len);
+    }
+
+    /// Reserves space for at least `additional` more bytes of encoded cells.
+    pub fn reserve(&mut self, additional: usize) {
+        self.string.reserve(additional);
+    }
+
+    /// Returns the position in the encoded list right after the first `n`
+    /// cells, or `None` if the list has less than `n` cells.
+    fn boundary(&self, n: usize) -> Option<usize> {
+        let mut iter = self.iter();
+
+        if n != 0 {
+            iter.nth(n - 1)?;
+        }
+
+        Some(iter.consumed())
+    }
+
+    /// Returns the encoded list.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.string
+    }
+
+    /// Returns the encoded list, consuming the value.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.string
+    }
+
+    /// Iterates over the cells of the list.
+    pub fn iter(&self) -> NelfIter<'_> {
+        match self.len {
+            Some(len) => NelfIter::with_len(&self.string, len),
+            None => NelfIter::from_string(&self.string),
+        }
+    }
+
+    /// Returns the number of cells in the list, which differs from the length
+    /// of the encoded list.
+    ///
+    /// The cells are only counted by parsing the whole list if it isn't
+    /// valid, otherwise their number is already known.
+    pub fn len_cells(&self) -> usize {
+        self.len.unwrap_or_else(|| self.iter().count())
+    }
+
+    /// Returns whether the list has no cells.
+    ///
+    /// Unlike the length of the encoded list, comments are not taken into
+    /// account, so a list made of comments only is empty.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns the cell at the index, or `None` if the list is shorter.
+    ///
+    /// The list is parsed up to the cell, so this takes time linear in the
+    /// length of the list rather than constant time.
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        self.iter().nth(index)
+    }
+
+    /// Returns the first cell, or `None` if the list is empty.
+    pub fn first(&self) -> Option<&[u8]> {
+        self.iter().next()
+    }
+
+    /// Returns the last cell, or `None` if the list is empty.
+    ///
+    /// The whole list is parsed from the start, like with [`NelfStr::last`].
+    ///
+    /// [`NelfStr::last`]: crate::NelfStr::last
+    pub fn last(&self) -> Option<&[u8]> {
+        self.iter().last()
+    }
+}
+
+/// List of cells that are not encoded.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NelfList(pub Vec<Vec<u8>>);
+
+/// List of cells borrowed from a validated encoded list.
+///
+/// Dereferences to the slice of its cells.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NelfListRef<'a>(Vec<&'a [u8]>);
+
+/// Counts the cells of the list, or returns `None` if it isn't valid.
+fn count_valid(string: &[u8]) -> Option<usize> {
+    validate(string).ok()?;
+    Some(NelfIter::from_string(string).count())
+}
+
+impl Default for Nelf {
+    fn default() -> Self {
+        Nelf::with_capacity(0)
+    }
+}
+
+/// Compares the encoded lists, comments included.
+impl PartialEq for Nelf {
+    fn eq(&self, other: &Self) -> bool {
+        self.string == other.string
+    }
+}
+
+impl Eq for Nelf {}
+
+impl Hash for Nelf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.string.hash(state);
+    }
+}
+
+impl Deref for Nelf {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.string
+    }
+}
+
+/// Returns the cell at the index, parsing the list up to it like
+/// [`Nelf::get`].
+///
+/// Since `Nelf` is indexed by cells, ranges of the encoded bytes have to be
+/// taken from [`Nelf::as_bytes`] instead.
+///
+/// # Panics
+///
+/// Panics if the list has `index` cells or less.
+///
+/// [`Nelf::as_bytes`]: Nelf::as_bytes
+/// [`Nelf::get`]: Nelf::get
+impl Index<usize> for Nelf {
+    type Output = [u8];
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(cell) => cell,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {index}",
+                self.len_cells(),
+            ),
+        }
+    }
+}
+
+/// Formats the cells like `["A", "B"]`, escaping the bytes that are not
+/// printable ASCII with [`ascii::escape_default`].
+///
+/// [`ascii::escape_default`]: core::ascii::escape_default
+impl Display for Nelf {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("[")?;
+
+        for (index, cell) in self.iter().enumerate() {
+            if index != 0 {
+                f.write_str(", ")?;
+            }
+
+            write_escaped(cell, f)?;
+        }
+
+        f.write_str("]")
+    }
+}
+
+/// Formats the cells as a list of byte strings like `[b"A", b"B"]`, rather
+/// than the encoded bytes.
+impl Debug for Nelf {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(ByteStr)).finish()
+    }
+}
+
+/// Cell formatted as a byte string literal by [`Debug`].
+struct ByteStr<'a>(&'a [u8]);
+
+impl Debug for ByteStr<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("b")?;
+        write_escaped(self.0, f)
+    }
+}
+
+/// Writes the cell in double quotes, escaping the bytes that are not
+/// printable ASCII.
+fn write_escaped(cell: &[u8], f: &mut Formatter) -> fmt::Result {
+    f.write_str("\"")?;
+
+    for &ch in cell {
+        Display::fmt(&ascii::escape_default(ch), f)?;
+    }
+
+    f.write_str("\"")
+}
+
+impl AsRef<[u8]> for Nelf {
+    fn as_ref(&self) -> &[u8] {
+        &self.string
+    }
+}
+
+impl From<Vec<Vec<u8>>> for Nelf {
+    fn from(cells: Vec<Vec<u8>>) -> Self {
+        Nelf::from_cells(cells)
+    }
+}
+
+impl IntoIterator for Nelf {
+    type Item = Vec<u8>;
+    type IntoIter = NelfIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        NelfIntoIter {
+            string: self.string,
+            index: 0,
+            remaining: self.len,
+        }
+    }
+}
+
+/// Iterator of owned copies of the cells of a [`Nelf`], consuming it.
+///
+/// [`Nelf`]: Nelf
+#[derive(Clone, Debug)]
+pub struct NelfIntoIter {
+    string: Vec<u8>,
+    index: usize,
+    remaining: Option<usize>,
+}
+
+impl Iterator for NelfIntoIter {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let string = &self.string[self.index..];
+        let mut iter = NelfIter::from_string(string);
+        let (range, _) = iter.next_range()?;
+
+        self.index += iter.consumed();
+
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= 1;
+        }
+
+        Some(string[range].to_vec())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => {
+                NelfIter::from_string(&self.string[self.index..]).size_hint()
+            }
+        }
+    }
+}
+
+impl<'a> From<Vec<&'a [u8]>> for Nelf {
+    fn from(cells: Vec<&'a [u8]>) -> Self {
+        Nelf::from_cells(cells)
+    }
+}
+
+impl TryFrom<&Nelf> for Vec<Vec<u8>> {
+    type Error = NelfError;
+
+    fn try_from(nelf: &Nelf) -> Result<Self, Self::Error> {
+        validate(nelf.as_bytes())?;
+        Ok(NelfIter::from_string(nelf.as_bytes())
+            .map(<[u8]>::to_vec)
+            .collect())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for NelfListRef<'a> {
+    type Error = NelfError;
+
+    fn try_from(string: &'a [u8]) -> Result<Self, Self::Error> {
+        validate(string)?;
+        Ok(NelfListRef(NelfIter::from_string(string).collect()))
+    }
+}
+
+impl<'a> Deref for NelfListRef<'a> {
+    type Target = [&'a [u8]];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use alloc::{format, vec};
+
+    use crate::{cells, validate, NelfError, NelfIter, ToNelf};
+
+    use super::{Nelf, NelfListRef};
+
+    #[test]
+    fn nelf_from_cells() {
+        let cells = vec![b"A".to_vec(), b"/|\\".to_vec(), Vec::new()];
+        let nelf = Nelf::from(cells.clone());
+        assert_eq!(nelf, Nelf::from_cells(&cells));
+        assert_eq!(Vec::try_from(&nelf), Ok(cells));
+    }
+
+    #[test]
+    fn nelf_from_vec() {
+        let nelf = Nelf::from(vec![vec![65], vec![66]]);
+        assert_eq!(cells!(nelf), [b"A", b"B"]);
+
+        let nelf = Nelf::from(vec![&b"A"[..], b"B"]);
+        assert_eq!(cells!(nelf), [b"A", b"B"]);
+    }
+
+    #[test]
+    fn nelf_into_iter() {
+        let cells = vec![b"A".to_vec(), b"/|\\".to_vec(), Vec::new()];
+        let nelf = Nelf::from(cells.clone());
+        assert_eq!(nelf.into_iter().collect::<Vec<_>>(), cells);
+        assert_eq!(Nelf::default().into_iter().next(), None);
+    }
+
+    #[test]
+    fn nelf_len_known() {
+        fn check(nelf: &Nelf) {
+            let len = NelfIter::from_string(nelf.as_bytes()).count();
+            assert_eq!(nelf.len_cells(), len, "{nelf:?}");
+            assert_eq!(nelf.iter().size_hint(), (len, Some(len)), "{nelf:?}");
+        }
+
+        let mut nelf = Nelf::from_cells([b"A", b"B"]);
+        check(&nelf);
+        nelf.push_cell(b"C");
+        nelf.insert_cell(1, b"");
+        check(&nelf);
+        nelf.remove_cell(0);
+        check(&nelf);
+        nelf.concat(&Nelf::from_cells([b"D"]));
+        nelf.extend_from_nelf(b" comment |E|");
+        check(&nelf);
+        nelf.retain(|cell| cell != b"C");
+        check(&nelf);
+        nelf.truncate_cells(2);
+        check(&nelf);
+
+        let mut iter = nelf.iter();
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        let mut iter = nelf.clone().into_iter();
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+
+        nelf.clear();
+        check(&nelf);
+        assert_eq!(Nelf::default().iter().size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn nelf_len_unknown() {
+        let mut nelf = Nelf::from_cells([b"A"]);
+        nelf.extend_from_nelf(b"|B");
+        assert_eq!(nelf.iter().size_hint(), (1, Some(2)));
+        assert_eq!(nelf.len_cells(), 2);
+        nelf.push_cell(b"C");
+        assert_eq!(nelf.len_cells(), 2);
+        assert_eq!(nelf.into_iter().size_hint(), (0, Some(4)));
+
+        let mut nelf = Nelf::from_bytes(b"|A|/B".to_vec());
+        assert_eq!(nelf.len_cells(), 2);
+        nelf.truncate_cells(1);
+        assert_eq!(nelf.iter().size_hint(), (1, Some(1)));
+        assert_eq!(nelf, Nelf::from_cells([b"A"]));
+    }
+
+    #[test]
+    fn nelf_is_empty() {
+        assert_eq!(Nelf::default().len_cells(), 0);
+        assert!(Nelf::default().is_empty());
+
+        let nelf = Nelf::from_cells([b"A", b"B"]);
+        assert_eq!(nelf.len_cells(), 2);
+        assert!(!nelf.is_empty());
+
+        let nelf = Nelf::from_bytes(b"comment".to_vec());
+        assert_eq!(nelf.len_cells(), 0);
+        assert!(nelf.is_empty());
+    }
+
+    #[test]
+    fn nelf_merge() {
+        let mut a = Nelf::from_cells([b"A", b"B"]);
+        let b = Nelf::from_cells([b"C"]);
+        assert_eq!(cells!(Nelf::merge(&a, &b)), [b"A", b"B", b"C"]);
+
+        a.concat(&b);
+        assert_eq!(a, Nelf::from_cells([b"A", b"B", b"C"]));
+    }
+
+    #[test]
+    fn nelf_extend_from_nelf() {
+        let mut nelf = Nelf::from_cells([b"A"]);
+        let other = Nelf::from_cells([&b"/|\\"[..], b""]);
+        nelf.extend_from_nelf(other.as_bytes());
+        nelf.extend_from_nelf(b"|B|");
+        assert_eq!(cells!(nelf), [&b"A"[..], b"/|\\", b"", b"B"]);
+    }
+
+    #[test]
+    fn nelf_retain() {
+        let mut nelf = Nelf::from_cells([b"A", b"B", b"A"]);
+        nelf.retain(|cell| cell == b"A");
+        assert_eq!(cells!(nelf), [b"A", b"A"]);
+    }
+
+    #[test]
+    fn nelf_insert_cell() {
+        let mut nelf = Nelf::from_cells([b"A", b"B"]);
+        nelf.insert_cell(1, b"X");
+        assert_eq!(cells!(nelf), [b"A", b"X", b"B"]);
+
+        nelf.insert_cell(0, "/|");
+        nelf.insert_cell(4, Vec::new());
+        assert_eq!(cells!(nelf), [&b"/|"[..], b"A", b"X", b"B", b""]);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 3) should be <= len (is 2)")]
+    fn nelf_insert_cell_out_of_bounds() {
+        Nelf::from_cells([b"A", b"B"]).insert_cell(3, b"X");
+    }
+
+    #[test]
+    fn nelf_remove_cell() {
+        let mut nelf = Nelf::from_cells([b"A", b"B"]);
+        assert_eq!(nelf.remove_cell(0).unwrap(), b"A");
+        assert_eq!(cells!(nelf), [b"B"]);
+        assert_eq!(nelf.remove_cell(1), None);
+
+        let mut nelf = Nelf::from_bytes(b"C|A|C/B\\C|D|".to_vec());
+        assert_eq!(nelf.remove_cell(1).unwrap(), b"B");
+        assert_eq!(nelf.as_bytes(), b"C|A|CC|D|");
+        assert_eq!(nelf.remove_cell(1).unwrap(), b"D");
+        assert_eq!(nelf.as_bytes(), b"C|A|CC");
+    }
+
+    #[test]
+    fn nelf_push_cell() {
+        let mut nelf = Nelf::with_capacity(16);
+        assert!(nelf.as_bytes().is_empty());
+
+        nelf.push_cell(b"A");
+        nelf.reserve(64);
+        nelf.push_cell("/|\\");
+        nelf.push_cell(Vec::new());
+        nelf.push_cell(&b"B"[..]);
+        assert_eq!(cells!(nelf), [&b"A"[..], b"/|\\", b"", b"B"]);
+        assert_eq!(nelf, Nelf::from_cells([&b"A"[..], b"/|\\", b"", b"B"]));
+    }
+
+    #[test]
+    fn nelf_truncate_cells() {
+        let cells = [&b"A"[..], b"/|\\", b"", b"B"];
+        let mut nelf = Nelf::from_cells(cells);
+        nelf.truncate_cells(4);
+        assert_eq!(nelf, Nelf::from_cells(cells));
+
+        nelf.truncate_cells(2);
+        assert_eq!(nelf, Nelf::from_cells(cells[..2].iter().copied()));
+        assert_eq!(cells!(nelf), [&b"A"[..], b"/|\\"]);
+        assert_eq!(validate(nelf.as_bytes()), Ok(()));
+
+        nelf.truncate_cells(0);
+        assert_eq!(nelf.len_cells(), 0);
+
+        let mut nelf = Nelf::from_bytes(b"|A|/B\\ comment".to_vec());
+        nelf.truncate_cells(2);
+        assert_eq!(nelf.as_bytes(), b"|A|/B\\ comment");
+        nelf.truncate_cells(3);
+        assert_eq!(nelf.as_bytes(), b"|A|/B\\ comment");
+        nelf.truncate_cells(1);
+        assert_eq!(nelf.as_bytes(), b"|A|");
+
+        let mut nelf = Nelf::from_bytes(b"comment".to_vec());
+        nelf.truncate_cells(0);
+        assert_eq!(nelf.as_bytes(), b"comment");
+
+        let mut nelf = Nelf::from_bytes(b"|A| |B| //".to_vec());
+        nelf.truncate_cells(2);
+        assert_eq!(nelf.as_bytes(), b"|A| |B| //");
+        assert_eq!(
+            validate(nelf.as_bytes()),
+            Err(NelfError::Unterminated { index: 2 })
+        );
+        nelf.truncate_cells(1);
+        assert_eq!(nelf.as_bytes(), b"|A|");
+        assert_eq!(validate(nelf.as_bytes()), Ok(()));
+
+        let mut nelf = Nelf::from_cells(cells);
+        nelf.clear();
+        assert_eq!(nelf, Nelf::default());
+    }
+
+    #[test]
+    fn nelf_display() {
+        let nelf = Nelf::from_cells([&b"A|"[..], b"\"\\\n\xFF"]);
+        assert_eq!(nelf.to_string(), r#"["A|", "\"\\\n\xff"]"#);
+        assert_eq!(Nelf::from_cells([b"A", b"B"]).to_string(), r#"["A", "B"]"#);
+        assert_eq!(Nelf::default().to_string(), "[]");
+    }
+
+    #[test]
+    fn nelf_debug() {
+        let nelf = Nelf::from_cells([&b"A"[..], b"/|\\"]);
+        assert_eq!(format!("{nelf:?}"), r#"[b"A", b"/|\\"]"#);
+        assert_eq!(format!("{:?}", Nelf::default()), "[]");
+    }
+
+    #[test]
+    fn nelf_into_bytes() {
+        let cells = [&b"A"[..], b"/|\\", b""];
+        let nelf = Nelf::from_cells(cells);
+        assert_eq!(nelf.iter().collect::<Vec<_>>(), cells);
+        assert_eq!(nelf.len_cells(), 3);
+        assert_eq!(Nelf::default().len_cells(), 0);
+
+        let string = nelf.clone().into_bytes();
+        assert_eq!(string, nelf.as_bytes());
+        assert_eq!(string, cells.to_nelf());
+    }
+
+    #[test]
+    fn nelf_deref() {
+        fn len(string: &[u8]) -> usize {
+            string.len()
+        }
+
+        let nelf = Nelf::from_cells([b"A", b"B"]);
+        assert_eq!(len(&nelf), 6);
+        assert_eq!(&*nelf, b"|A||B|");
+        assert_eq!(nelf.as_ref(), nelf.as_bytes());
+        assert_eq!(cells!(nelf), [b"A", b"B"]);
+    }
+
+    #[test]
+    fn nelf_as_bytes() {
+        let nelf = Nelf::from_cells([&b"AB"[..], b"C"]);
+        assert_eq!(nelf.as_bytes(), b"|AB||C|");
+        assert_eq!(nelf.as_ref(), nelf.as_bytes());
+        assert_eq!(nelf.as_bytes().len(), 7);
+        assert_eq!(nelf.len_cells(), 2);
+        assert_eq!(nelf.get(0), Some(&b"AB"[..]));
+        assert_eq!(&nelf[1], b"C");
+        assert_eq!(cells!(nelf), [&b"AB"[..], b"C"]);
+    }
+
+    #[test]
+    fn nelf_index() {
+        let nelf = Nelf::from_cells([&b"A"[..], b"/|\\", b""]);
+        assert_eq!(&nelf[0], b"A");
+        assert_eq!(&nelf[1], b"/|\\");
+        assert_eq!(&nelf[2], b"");
+        assert_eq!(nelf.get(2), Some(&b""[..]));
+        assert_eq!(nelf.get(3), None);
+    }
+
+    #[test]
+    fn nelf_first_last() {
+        let nelf = Nelf::from_cells([&b"AB"[..], b"/|\\", b"C"]);
+        assert_eq!(nelf.first(), Some(&b"AB"[..]));
+        assert_eq!(nelf.last(), Some(&b"C"[..]));
+        assert_eq!(Nelf::default().first(), None);
+        assert_eq!(Nelf::default().last(), None);
+        assert_eq!(Nelf::from_bytes(b"comment".to_vec()).last(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 3 but the index is 3")]
+    fn nelf_index_out_of_bounds() {
+        let nelf = Nelf::from_cells([&b"A"[..], b"/|\\", b""]);
+        let _ = &nelf[3];
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn nelf_send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>(value: T) -> T {
+            value
+        }
+
+        let nelf = assert_send_sync(Nelf::from_cells([&b"A"[..], b"/|\\"]));
+        let cells = std::thread::spawn(move || {
+            nelf.iter().map(<[u8]>::to_vec).collect::<Vec<_>>()
+        })
+        .join()
+        .unwrap();
+        assert_eq!(cells, [&b"A"[..], b"/|\\"]);
+    }
+
+    #[test]
+    fn nelf_into_cells() {
+        assert_eq!(
+            Vec::try_from(&Nelf::from_bytes(b"|A||B".to_vec())),
+            Err(NelfError::Unterminated { index: 1 })
+        );
+    }
+
+    #[test]
+    fn nelf_list_ref_1() {
+        let list = NelfListRef::try_from(&b"C|A|C/B\\C"[..]).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0], b"A");
+        assert_eq!(list[1], b"B");
+        assert_eq!(list.get(2), None);
+    }
+
+    #[test]
+    fn nelf_list_ref_2() {
+        assert_eq!(
+            NelfListRef::try_from(&b"|A|/B"[..]),
+            Err(NelfError::Unterminated { index: 1 })
+        );
+    }
+}
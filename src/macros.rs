@@ -0,0 +1,104 @@
+/// Encodes the values as a NELF list, returning it as a `Vec<u8>`.
+///
+/// Accepts any expressions implementing [`ToCell`], which may be of
+/// different types.
+///
+/// ```
+/// use nelf::{nelf, NelfIter};
+///
+/// let list = nelf![b"A", "B", vec![b'C']];
+/// assert_eq!(list, b"|A||B||C|");
+/// assert_eq!(NelfIter::from_string(&list).count(), 3);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToCell`]: crate::ToCell
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+#[macro_export]
+macro_rules! nelf {
+    ($($cell:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut list = $crate::__macros::Vec::<u8>::new();
+        $($crate::__macros::push_cell(&mut list, $cell);)*
+        list
+    }};
+}
+
+/// Decodes the cells of an encoded list into a `Vec<&[u8]>`.
+///
+/// Accepts anything implementing `AsRef<[u8]>`. The cells borrow from it, so
+/// a temporary such as the result of [`nelf!`] is only usable within the
+/// same statement.
+///
+/// ```
+/// use nelf::{cells, nelf};
+///
+/// assert_eq!(cells!(b"|A|/|\\"), [b"A", b"|"]);
+/// assert_eq!(cells!(nelf![b"A", b"B"]), [b"A", b"B"]);
+/// ```
+///
+/// [`nelf!`]: crate::nelf
+#[macro_export]
+macro_rules! cells {
+    ($string:expr) => {
+        $crate::NelfIter::from_string(::core::convert::AsRef::<[u8]>::as_ref(
+            &$string,
+        ))
+        .collect::<$crate::__macros::Vec<_>>()
+    };
+}
+
+/// Items used by the expansions of the macros.
+#[doc(hidden)]
+pub mod __macros {
+    pub use alloc::vec::Vec;
+
+    use crate::encode::encode_cell;
+    use crate::ToCell;
+
+    pub fn push_cell<T: ToCell>(list: &mut Vec<u8>, cell: T) {
+        encode_cell(cell.content(), list);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::{NelfIter, ToNelf};
+
+    #[test]
+    fn nelf_macro_1() {
+        let list = nelf![b"A", b"B"];
+        assert_eq!(
+            NelfIter::from_string(&list).collect::<Vec<_>>(),
+            [b"A", b"B"]
+        );
+        assert_eq!(list, [b"A", b"B"].to_nelf());
+    }
+
+    #[test]
+    fn nelf_macro_mixed() {
+        let list =
+            nelf![b"A", "B", vec![1, 2, 3], String::from("|"), &b"/"[..],];
+        assert_eq!(
+            NelfIter::from_string(&list).collect::<Vec<_>>(),
+            [&b"A"[..], b"B", &[1, 2, 3], b"|", b"/"]
+        );
+        assert_eq!(nelf![], b"");
+    }
+
+    #[test]
+    fn cells_macro_1() {
+        let list = [&b"A"[..], b"/|\\", b""].to_nelf();
+        assert_eq!(cells!(list), [&b"A"[..], b"/|\\", b""]);
+        assert_eq!(cells!(&list[..3]), [b"A"]);
+        assert!(cells!(b"").is_empty());
+    }
+}
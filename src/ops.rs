@@ -0,0 +1,220 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(feature = "alloc")]
+use crate::encode::encode_cell;
+#[cfg(feature = "alloc")]
+use crate::encode_list;
+#[cfg(feature = "std")]
+use crate::NelfError;
+use crate::NelfIter;
+
+/// Collects the list of alternating keys and values into a map.
+///
+/// Later values replace earlier ones with the same key. Returns an error if
+/// the list contains an odd number of cells.
+#[cfg(feature = "std")]
+pub fn collect_pairs(
+    string: &[u8],
+) -> Result<HashMap<Vec<u8>, Vec<u8>>, NelfError> {
+    let mut result = HashMap::new();
+    let mut iter = NelfIter::from_string(string);
+    let mut count = 0;
+
+    while let Some(key) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or(NelfError::OddCount { count: count + 1 })?;
+        result.insert(key.to_vec(), value.to_vec());
+        count += 2;
+    }
+
+    Ok(result)
+}
+
+/// Folds the cells of the list with the function, in order.
+///
+/// The cells borrow from the string, so nothing is allocated.
+pub fn fold_cells<B, F>(string: &[u8], init: B, f: F) -> B
+where
+    F: FnMut(B, &[u8]) -> B,
+{
+    NelfIter::from_string(string).fold(init, f)
+}
+
+/// Returns whether any cell of the list is equal to the needle.
+///
+/// Parsing stops at the first matching cell.
+pub fn contains_cell(string: &[u8], needle: &[u8]) -> bool {
+    NelfIter::from_string(string).any(|cell| cell == needle)
+}
+
+/// Returns the index of the first cell equal to the needle, or `None` if
+/// there is no such cell.
+pub fn position_of(string: &[u8], needle: &[u8]) -> Option<usize> {
+    NelfIter::from_string(string).position(|cell| cell == needle)
+}
+
+/// Splits the list into the part holding its first `index` cells and the
+/// part holding the rest.
+///
+/// The list is split right after the closing run of the last cell of the
+/// first part, so both parts are valid lists if the list is. Whatever follows
+/// that run lands in the second part, so with exactly `index` cells it holds
+/// the comments after the last one. If the list has less than `index` cells,
+/// the second part is empty.
+pub fn split_at(string: &[u8], index: usize) -> (&[u8], &[u8]) {
+    let mut iter = NelfIter::from_string(string);
+
+    if index != 0 && iter.nth(index - 1).is_none() {
+        return (string, &[]);
+    }
+
+    string.split_at(iter.consumed())
+}
+
+/// Applies the function to each cell of the list, encoding the results as a
+/// new list in the same order.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+#[cfg(feature = "alloc")]
+pub fn map_cells<F>(string: &[u8], mut f: F) -> Vec<u8>
+where
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    let mut result = Vec::new();
+
+    for cell in NelfIter::from_string(string) {
+        encode_cell(&f(cell), &mut result);
+    }
+
+    result
+}
+
+/// Encodes the cells of the list for which the predicate returns `true` as a
+/// new list, in the same order.
+#[cfg(feature = "alloc")]
+pub fn filter_cells<F>(string: &[u8], mut f: F) -> Vec<u8>
+where
+    F: FnMut(&[u8]) -> bool,
+{
+    let mut result = Vec::new();
+
+    for cell in NelfIter::from_string(string).filter(|cell| f(cell)) {
+        encode_cell(cell, &mut result);
+    }
+
+    result
+}
+
+/// Encodes the cells of the list as a new list in reverse order.
+#[cfg(feature = "alloc")]
+pub fn reverse_cells(string: &[u8]) -> Vec<u8> {
+    let cells: Vec<_> = NelfIter::from_string(string).collect();
+    encode_list(cells.iter().rev().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use crate::cells;
+    #[cfg(feature = "std")]
+    use crate::NelfError;
+    #[cfg(feature = "alloc")]
+    use crate::ToNelf;
+
+    #[cfg(feature = "std")]
+    use super::collect_pairs;
+    use super::{contains_cell, fold_cells, position_of, split_at};
+    #[cfg(feature = "alloc")]
+    use super::{filter_cells, map_cells, reverse_cells};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn collect_pairs_1() {
+        let map = collect_pairs(&[b"A", b"1", b"B", b"2"].to_nelf()).unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&b"A"[..]], b"1");
+        assert_eq!(map[&b"B"[..]], b"2");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn collect_pairs_2() {
+        assert_eq!(
+            collect_pairs(&[b"A", b"1", b"B"].to_nelf()),
+            Err(NelfError::OddCount { count: 3 })
+        );
+    }
+
+    #[test]
+    fn fold_cells_1() {
+        let string = b"|A|/|\\/\\|BC|";
+        assert_eq!(fold_cells(string, 0, |len, cell| len + cell.len()), 4);
+        assert_eq!(fold_cells(b"", 1, |len, cell| len + cell.len()), 1);
+    }
+
+    #[test]
+    fn contains_cell_1() {
+        let string = b"|A||B|";
+        assert!(contains_cell(string, b"B"));
+        assert!(!contains_cell(string, b"C"));
+        assert!(!contains_cell(string, b"|"));
+        assert!(contains_cell(b"|A|/\\", b""));
+    }
+
+    #[test]
+    fn position_of_1() {
+        let string = b"|A||B||C|";
+        assert_eq!(position_of(string, b"B"), Some(1));
+        assert_eq!(position_of(b"|A||B||B|", b"B"), Some(1));
+        assert_eq!(position_of(string, b"D"), None);
+    }
+
+    #[test]
+    fn split_at_1() {
+        let string = b"|A||B||C|";
+        assert_eq!(split_at(string, 1), (&b"|A|"[..], &b"|B||C|"[..]));
+        assert_eq!(split_at(string, 0), (&b""[..], &string[..]));
+        assert_eq!(split_at(string, 3), (&string[..], &b""[..]));
+        assert_eq!(split_at(string, 4), (&string[..], &b""[..]));
+        assert_eq!(split_at(b"|A| - /B", 1), (&b"|A|"[..], &b" - /B"[..]));
+        assert_eq!(split_at(b"|A| - ", 1), (&b"|A|"[..], &b" - "[..]));
+        assert_eq!(split_at(b"|A| - ", 2), (&b"|A| - "[..], &b""[..]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn map_cells_1() {
+        let string = [b"a", b"b"].to_nelf();
+        let result = map_cells(&string, <[u8]>::to_ascii_uppercase);
+        assert_eq!(cells!(result), [b"A", b"B"]);
+
+        let result = map_cells(&string, |cell| [cell, b"|"].concat());
+        assert_eq!(cells!(result), [b"a|", b"b|"]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn filter_cells_1() {
+        let string = [&b"A"[..], b"BC", b"", b"/|\\", b"|"].to_nelf();
+        let result = filter_cells(&string, |cell| cell.len() > 1);
+        assert_eq!(cells!(result), [&b"BC"[..], b"/|\\"]);
+        assert_eq!(filter_cells(&string, |_| false), b"");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reverse_cells_1() {
+        let result = reverse_cells(&[b"A", b"B", b"C"].to_nelf());
+        assert_eq!(cells!(result), [b"C", b"B", b"A"]);
+        assert_eq!(reverse_cells(b""), b"");
+    }
+}
@@ -0,0 +1,42 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::NelfIter;
+
+/// Decodes the cells of the list, dereferencing them in parallel.
+///
+/// The ranges of the cells are found sequentially first, since a cell can
+/// only be found once the previous one has been skipped. The cells are
+/// returned in the same order as [`NelfIter`] yields them.
+///
+/// [`NelfIter`]: NelfIter
+pub fn par_decode(string: &[u8]) -> Vec<&[u8]> {
+    let mut iter = NelfIter::from_string(string);
+    let mut ranges = Vec::new();
+
+    while let Some((range, _)) = iter.next_range() {
+        ranges.push(range);
+    }
+
+    ranges.into_par_iter().map(|range| &string[range]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NelfIter, ToNelf};
+
+    use super::par_decode;
+
+    #[test]
+    fn par_decode_1() {
+        let cells: Vec<Vec<u8>> =
+            (0..10000).map(|i| b"A|/\\".repeat(i % 7)).collect();
+        let mut string = (&cells).to_nelf();
+        string.extend_from_slice(b"/B");
+
+        assert_eq!(
+            par_decode(&string),
+            NelfIter::from_string(&string).collect::<Vec<_>>()
+        );
+        assert_eq!(par_decode(&string).last().unwrap(), b"B");
+    }
+}
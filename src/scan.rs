@@ -0,0 +1,368 @@
+#[cfg(feature = "simd")]
+use wide::u8x16;
+
+/// Whether each byte value is one of the delimiters.
+pub(crate) const IS_DELIM: [bool; 256] = {
+    let mut table = [false; 256];
+    table[b'|' as usize] = true;
+    table[b'/' as usize] = true;
+    table[b'\\' as usize] = true;
+    table
+};
+
+#[cfg(any(feature = "simd", not(feature = "memchr")))]
+const WINDOW: usize = 64;
+
+#[cfg(feature = "simd")]
+const LANES: usize = 16;
+
+/// Returns the index of the first delimiter byte in the string.
+#[cfg(feature = "simd")]
+pub(crate) fn find_delimiter(string: &[u8]) -> Option<usize> {
+    find_simd(string, |chunk| {
+        chunk.cmp_eq(u8x16::splat(b'|'))
+            | chunk.cmp_eq(u8x16::splat(b'/'))
+            | chunk.cmp_eq(u8x16::splat(b'\\'))
+    })
+    .or_else(|| tail(string, find_delimiter_scalar))
+}
+
+/// Returns the index of the first delimiter byte in the string.
+#[cfg(all(feature = "memchr", not(feature = "simd")))]
+pub(crate) fn find_delimiter(string: &[u8]) -> Option<usize> {
+    memchr::memchr3(b'|', b'/', b'\\', string)
+}
+
+/// Returns the index of the first delimiter byte in the string.
+#[cfg(not(any(feature = "memchr", feature = "simd")))]
+pub(crate) fn find_delimiter(string: &[u8]) -> Option<usize> {
+    find_delimiter_scalar(string)
+}
+
+/// Returns the index of the first occurrence of the byte in the string.
+#[cfg(feature = "simd")]
+pub(crate) fn find_byte(string: &[u8], byte: u8) -> Option<usize> {
+    find_simd(string, |chunk| chunk.cmp_eq(u8x16::splat(byte)))
+        .or_else(|| tail(string, |tail| find_byte_scalar(tail, byte)))
+}
+
+/// Returns the index of the first occurrence of the byte in the string.
+#[cfg(all(feature = "memchr", not(feature = "simd")))]
+pub(crate) fn find_byte(string: &[u8], byte: u8) -> Option<usize> {
+    memchr::memchr(byte, string)
+}
+
+/// Returns the index of the first occurrence of the byte in the string.
+#[cfg(not(any(feature = "memchr", feature = "simd")))]
+pub(crate) fn find_byte(string: &[u8], byte: u8) -> Option<usize> {
+    find_byte_scalar(string, byte)
+}
+
+/// Returns the lengths of the longest runs of the delimiters in the string.
+#[cfg(feature = "simd")]
+pub(crate) fn max_runs(string: &[u8]) -> [usize; 3] {
+    [b'|', b'/', b'\\'].map(|delimiter| max_run_simd(string, delimiter))
+}
+
+/// Returns the lengths of the longest runs of the delimiters in the string.
+#[cfg(not(feature = "simd"))]
+pub(crate) fn max_runs(string: &[u8]) -> [usize; 3] {
+    max_runs_scalar(string)
+}
+
+#[cfg(any(feature = "simd", not(feature = "memchr")))]
+fn find_delimiter_scalar(string: &[u8]) -> Option<usize> {
+    find_windowed(string, |ch| IS_DELIM[ch as usize])
+}
+
+#[cfg(any(feature = "simd", not(feature = "memchr")))]
+fn find_byte_scalar(string: &[u8], byte: u8) -> Option<usize> {
+    find_windowed(string, |ch| ch == byte)
+}
+
+/// Returns the index of the first byte matching the predicate, checking
+/// windows of the string as a whole before looking for the byte in them.
+///
+/// The check of a window doesn't stop at the first match, which lets it be
+/// vectorized, while windows without matches are skipped with a single
+/// branch.
+#[cfg(any(feature = "simd", not(feature = "memchr")))]
+fn find_windowed(string: &[u8], matches: impl Fn(u8) -> bool) -> Option<usize> {
+    string
+        .chunks(WINDOW)
+        .enumerate()
+        .find_map(|(index, window)| {
+            if !window.iter().fold(false, |found, &ch| found | matches(ch)) {
+                return None;
+            }
+
+            window
+                .iter()
+                .position(|&ch| matches(ch))
+                .map(|offset| index * WINDOW + offset)
+        })
+}
+
+#[cfg_attr(feature = "simd", allow(dead_code))]
+fn max_runs_scalar(string: &[u8]) -> [usize; 3] {
+    let mut max = [0; 3];
+    let mut run = [0; 3];
+
+    for &ch in string {
+        for (index, delimiter) in [b'|', b'/', b'\\'].into_iter().enumerate() {
+            if ch == delimiter {
+                run[index] += 1;
+                max[index] = max[index].max(run[index]);
+            } else {
+                run[index] = 0;
+            }
+        }
+    }
+
+    max
+}
+
+/// Returns the length of the longest run of the byte in the string, looking
+/// at whole chunks of the string at once.
+#[cfg(feature = "simd")]
+fn max_run_simd(string: &[u8], byte: u8) -> usize {
+    let needle = u8x16::splat(byte);
+    let mut chunks = string.chunks_exact(LANES);
+    let mut max = 0;
+    let mut run = 0;
+
+    for chunk in &mut chunks {
+        let chunk = u8x16::new(chunk.try_into().unwrap());
+        let mask = chunk.cmp_eq(needle).move_mask() as u32;
+
+        if mask == (1 << LANES) - 1 {
+            run += LANES;
+            continue;
+        }
+
+        max = max.max(run + mask.trailing_ones() as usize);
+
+        // Longest run of set bits inside the mask, found by shortening every
+        // run by one bit per step.
+        let mut bits = mask;
+        let mut inner = 0;
+
+        while bits != 0 {
+            bits &= bits << 1;
+            inner += 1;
+        }
+
+        max = max.max(inner);
+        run = (mask << (32 - LANES)).leading_ones() as usize;
+    }
+
+    for &ch in chunks.remainder() {
+        if ch == byte {
+            run += 1;
+        } else {
+            max = max.max(run);
+            run = 0;
+        }
+    }
+
+    max.max(run)
+}
+
+/// Searches the whole chunks of the string for the first byte matched by the
+/// mask function.
+#[cfg(feature = "simd")]
+fn find_simd(string: &[u8], mask: impl Fn(u8x16) -> u8x16) -> Option<usize> {
+    string
+        .chunks_exact(LANES)
+        .enumerate()
+        .find_map(|(index, chunk)| {
+            let chunk = u8x16::new(chunk.try_into().unwrap());
+            match mask(chunk).move_mask() {
+                0 => None,
+                bits => Some(index * LANES + bits.trailing_zeros() as usize),
+            }
+        })
+}
+
+/// Searches the bytes left over after the whole chunks of the string.
+#[cfg(feature = "simd")]
+fn tail(
+    string: &[u8],
+    find: impl FnOnce(&[u8]) -> Option<usize>,
+) -> Option<usize> {
+    let start = string.len() - string.len() % LANES;
+    find(&string[start..]).map(|index| start + index)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+
+    #[cfg(feature = "alloc")]
+    use crate::NelfIter;
+
+    #[cfg(all(feature = "alloc", feature = "simd"))]
+    use super::{
+        find_byte, find_byte_scalar, find_delimiter_scalar, max_runs,
+        max_runs_scalar,
+    };
+    use super::{find_delimiter, IS_DELIM};
+
+    /// Straightforward byte by byte parser the accelerated one is checked
+    /// against.
+    #[cfg(feature = "alloc")]
+    fn parse_scalar(string: &[u8]) -> Vec<&[u8]> {
+        let mut cells = Vec::new();
+        let mut index = 0;
+
+        while let Some(offset) = string[index..]
+            .iter()
+            .position(|&ch| matches!(ch, b'|' | b'/' | b'\\'))
+        {
+            index += offset;
+            let lch = string[index];
+            let Some(len) = string[index..].iter().position(|&ch| ch != lch)
+            else {
+                break;
+            };
+            let rch = match lch {
+                b'|' => b'|',
+                b'/' => b'\\',
+                _ => b'/',
+            };
+
+            let start = index + len;
+            let mut count = 0;
+            index = start;
+
+            while index < string.len() && count < len {
+                count = if string[index] == rch { count + 1 } else { 0 };
+                index += 1;
+            }
+
+            cells.push(if count == len {
+                &string[start..index - len]
+            } else {
+                &string[start..]
+            });
+        }
+
+        cells
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn max_runs_simd_matches_scalar() {
+        let mut state = 0x9e3779b9u32;
+        let mut random = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        for _ in 0..1000 {
+            let len = random() as usize % 300;
+            let bias = random() as usize % 8 + 1;
+            let string: Vec<u8> = (0..len)
+                .map(|_| match random() as usize % (bias + 2) {
+                    0 => b'A',
+                    1 => b'/',
+                    2 => b'\\',
+                    _ => b'|',
+                })
+                .collect();
+
+            assert_eq!(max_runs(&string), max_runs_scalar(&string));
+        }
+
+        assert_eq!(max_runs(&[b'|'; 100]), [100, 0, 0]);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn simd_matches_scalar() {
+        let mut state = 0x2545f491u32;
+        let mut random = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        for _ in 0..1000 {
+            let len = random() as usize % 200;
+            let string: Vec<u8> =
+                (0..len).map(|_| b"|/\\AB"[random() as usize % 5]).collect();
+
+            assert_eq!(find_delimiter(&string), find_delimiter_scalar(&string));
+            assert_eq!(
+                find_byte(&string, b'A'),
+                find_byte_scalar(&string, b'A')
+            );
+            assert_eq!(
+                NelfIter::from_string(&string).collect::<Vec<_>>(),
+                parse_scalar(&string)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_large_buffer() {
+        let mut state = 0x1b873593u32;
+        let string: Vec<u8> = (0..4 << 20)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                match state % 512 {
+                    0..=2 => b'|',
+                    3 => b'/',
+                    4 => b'\\',
+                    _ => b'A',
+                }
+            })
+            .collect();
+
+        let cells = NelfIter::from_string(&string).collect::<Vec<_>>();
+        assert!(cells.len() > 1000);
+        assert_eq!(cells, parse_scalar(&string));
+    }
+
+    #[test]
+    fn is_delim_1() {
+        for ch in 0..=u8::MAX {
+            assert_eq!(
+                IS_DELIM[ch as usize],
+                matches!(ch, b'|' | b'/' | b'\\')
+            );
+        }
+    }
+
+    #[test]
+    fn find_delimiter_1() {
+        assert_eq!(find_delimiter(b""), None);
+        assert_eq!(find_delimiter(b"ABC"), None);
+        assert_eq!(find_delimiter(b"AB\\C|"), Some(2));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn find_delimiter_long_run() {
+        let mut string = vec![b'A'; 1 << 20];
+        string.extend_from_slice(b"|B| /C\\");
+        string.extend(vec![b'D'; 1 << 20]);
+
+        assert_eq!(find_delimiter(&string), Some(1 << 20));
+        assert_eq!(
+            NelfIter::from_string(&string).collect::<Vec<_>>(),
+            [b"B", b"C"]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use ::serde::de::{Error as DeError, SeqAccess, Visitor};
+use ::serde::ser::Error as SerError;
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{validate, Nelf, NelfList};
+
+pub use de::from_nelf_serde;
+pub use ser::to_nelf_serde;
+
+mod de;
+mod ser;
+
+/// Adaptor serializing a list of cells as a sequence of byte strings.
+///
+/// By default, serde serializes byte vectors as sequences of integers, which
+/// is wasteful in binary formats. This module is meant to be used with
+/// `#[serde(with = "nelf::serde_bytes_cells")]` on a `Vec<Vec<u8>>` field.
+pub mod serde_bytes_cells {
+    use ::serde::{Deserializer, Serialize, Serializer};
+
+    use super::{Bytes, CellsVisitor};
+
+    /// Serializes the cells as a sequence of byte strings.
+    pub fn serialize<S, T>(
+        cells: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsRef<[u8]>,
+    {
+        serializer.collect_seq(cells.iter().map(|cell| Bytes(cell.as_ref())))
+    }
+
+    /// Deserializes the cells from a sequence of byte strings or byte
+    /// sequences.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        deserializer.deserialize_seq(CellsVisitor)
+    }
+
+    impl Serialize for Bytes<'_> {
+        fn serialize<S: Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+}
+
+/// Error returned when serializing a value as or deserializing a value from a
+/// NELF list fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerdeError(String);
+
+impl Display for SerdeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for SerdeError {}
+
+impl SerError for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerdeError(msg.to_string())
+    }
+}
+
+impl DeError for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerdeError(msg.to_string())
+    }
+}
+
+impl Serialize for Nelf {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
+
+impl Serialize for NelfList {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Reads the encoded list as a byte string, the way it is serialized, rather
+/// than as a sequence of cells, which [`NelfList`] reads instead.
+///
+/// Lists with unterminated cells are rejected, as [`validate`] does.
+///
+/// [`NelfList`]: crate::NelfList
+/// [`validate`]: crate::validate
+impl<'de> Deserialize<'de> for Nelf {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let Cell(bytes) = deserializer.deserialize_byte_buf(CellVisitor)?;
+        validate(&bytes).map_err(D::Error::custom)?;
+        Ok(Nelf::from_bytes(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for NelfList {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Ok(NelfList(deserializer.deserialize_seq(CellsVisitor)?))
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+struct Cell(Vec<u8>);
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(CellVisitor)
+    }
+}
+
+struct CellVisitor;
+
+impl<'de> Visitor<'de> for CellVisitor {
+    type Value = Cell;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: DeError>(self, v: &[u8]) -> Result<Cell, E> {
+        Ok(Cell(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: DeError>(self, v: Vec<u8>) -> Result<Cell, E> {
+        Ok(Cell(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Cell, A::Error> {
+        let mut cell =
+            Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+
+        while let Some(byte) = seq.next_element()? {
+            cell.push(byte);
+        }
+
+        Ok(Cell(cell))
+    }
+}
+
+struct CellsVisitor;
+
+impl<'de> Visitor<'de> for CellsVisitor {
+    type Value = Vec<Vec<u8>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of byte arrays")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        let mut cells =
+            Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+
+        while let Some(Cell(cell)) = seq.next_element()? {
+            cells.push(cell);
+        }
+
+        Ok(cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+    use serde_test::{assert_tokens, Token};
+
+    use crate::{Nelf, NelfIter, NelfList};
+
+    #[test]
+    fn serialize_nelf() {
+        let nelf = Nelf::from_cells([b"A", b"|"]);
+        assert_eq!(
+            serde_json::to_string(&nelf).unwrap(),
+            "[124,65,124,47,124,92]"
+        );
+        assert_tokens(&nelf, &[Token::Bytes(b"|A|/|\\")]);
+    }
+
+    #[test]
+    fn serialize_nelf_list() {
+        let list = NelfList(vec![b"A".to_vec(), b"|".to_vec()]);
+        assert_eq!(serde_json::to_string(&list).unwrap(), "[[65],[124]]");
+    }
+
+    #[test]
+    fn deserialize_nelf() {
+        let nelf: Nelf = serde_json::from_str("[124,65,124,47,92]").unwrap();
+        assert_eq!(
+            NelfIter::from_string(nelf.as_bytes()).collect::<Vec<_>>(),
+            [&b"A"[..], b""]
+        );
+        assert!(serde_json::from_str::<Nelf>("[[65]]").is_err());
+        assert!(serde_json::from_str::<Nelf>("[256]").is_err());
+    }
+
+    #[test]
+    fn deserialize_nelf_unterminated() {
+        for string in ["[124,65]", "[124,65,124,47]", "[124,65,124,47,66]"] {
+            let error = serde_json::from_str::<Nelf>(string).unwrap_err();
+            assert!(error.to_string().contains("not terminated"), "{error}");
+        }
+
+        assert!(postcard::from_bytes::<Nelf>(&[2, b'|', b'A']).is_err());
+        assert!(serde_json::from_str::<Nelf>("[67,124,65,124,67]").is_ok());
+    }
+
+    #[test]
+    fn nelf_postcard() {
+        let nelf = Nelf::from_cells([&b"A"[..], b"/|\\", b""]);
+        let bytes = postcard::to_allocvec(&nelf).unwrap();
+        assert_eq!(bytes[0] as usize, nelf.as_bytes().len());
+        assert_eq!(bytes[1..], *nelf.as_bytes());
+        assert_eq!(postcard::from_bytes::<Nelf>(&bytes).unwrap(), nelf);
+    }
+
+    #[test]
+    fn deserialize_nelf_list() {
+        let list: NelfList = serde_json::from_str("[[65],[124]]").unwrap();
+        assert_eq!(list.0, [b"A", b"|"]);
+        assert!(serde_json::from_str::<NelfList>(r#"[["A"]]"#).is_err());
+    }
+
+    #[test]
+    fn serde_bytes_cells() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Cells(#[serde(with = "crate::serde_bytes_cells")] Vec<Vec<u8>>);
+
+        assert_tokens(
+            &Cells(vec![b"A".to_vec(), b"".to_vec()]),
+            &[
+                Token::NewtypeStruct { name: "Cells" },
+                Token::Seq { len: Some(2) },
+                Token::Bytes(b"A"),
+                Token::Bytes(b""),
+                Token::SeqEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use std::str;
+
+use ::serde::de::{
+    self, DeserializeSeed, Error, IntoDeserializer, SeqAccess, Visitor,
+};
+use ::serde::{forward_to_deserialize_any, Deserialize};
+
+use crate::serde::SerdeError;
+use crate::NelfIter;
+
+/// Deserializes a value from the cells contained in the NELF list.
+///
+/// The list is presented as a sequence of its cells, which supports the same
+/// subset of types as [`to_nelf_serde`]. Strings and byte arrays borrow from
+/// the list where possible.
+///
+/// [`to_nelf_serde`]: crate::to_nelf_serde
+pub fn from_nelf_serde<'de, T: Deserialize<'de>>(
+    bytes: &'de [u8],
+) -> Result<T, SerdeError> {
+    T::deserialize(Deserializer(bytes))
+}
+
+struct Deserializer<'de>(&'de [u8]);
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        let mut cells = Cells(NelfIter::from_string(self.0));
+        let value = visitor.visit_seq(&mut cells)?;
+
+        match cells.0.next() {
+            Some(_) => Err(SerdeError::custom("trailing cells in NELF list")),
+            None => Ok(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple tuple_struct map
+        struct enum identifier ignored_any
+    }
+}
+
+struct Cells<'de>(NelfIter<'de>);
+
+impl<'de> SeqAccess<'de> for Cells<'de> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, SerdeError> {
+        self.0
+            .next()
+            .map(|cell| seed.deserialize(CellDeserializer(cell)))
+            .transpose()
+    }
+}
+
+struct CellDeserializer<'de>(&'de [u8]);
+
+impl<'de> CellDeserializer<'de> {
+    fn str(&self) -> Result<&'de str, SerdeError> {
+        str::from_utf8(self.0).map_err(SerdeError::custom)
+    }
+}
+
+macro_rules! parse {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                visitor: V,
+            ) -> Result<V::Value, SerdeError> {
+                visitor.$visit(self.str()?.parse().map_err(SerdeError::custom)?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for CellDeserializer<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match str::from_utf8(self.0) {
+            Ok(string) => visitor.visit_borrowed_str(string),
+            Err(_) => visitor.visit_borrowed_bytes(self.0),
+        }
+    }
+
+    parse! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_borrowed_str(self.str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_borrowed_bytes(self.0)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        if self.0.is_empty() {
+            visitor.visit_unit()
+        } else {
+            Err(SerdeError::invalid_length(self.0.len(), &"an empty cell"))
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_enum(self.str()?.into_deserializer())
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        seq tuple tuple_struct map struct identifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ToNelf;
+
+    use super::from_nelf_serde;
+
+    #[test]
+    fn from_nelf_serde_1() {
+        let nelf = [&b"A"[..], b"B|C"].to_nelf();
+        assert_eq!(
+            from_nelf_serde::<Vec<String>>(&nelf).unwrap(),
+            ["A", "B|C"]
+        );
+        assert_eq!(
+            from_nelf_serde::<(String, &str)>(&nelf).unwrap(),
+            ("A".to_string(), "B|C")
+        );
+    }
+
+    #[test]
+    fn from_nelf_serde_2() {
+        let nelf = [&b"1"[..], b"-2.5", b"A", b"true", b""].to_nelf();
+        assert_eq!(
+            from_nelf_serde::<(u8, f64, char, bool, Option<u8>)>(&nelf)
+                .unwrap(),
+            (1, -2.5, 'A', true, None)
+        );
+    }
+
+    #[test]
+    fn from_nelf_serde_3() {
+        let nelf = [b"A", b"B"].to_nelf();
+        assert!(from_nelf_serde::<(String,)>(&nelf).is_err());
+        assert!(from_nelf_serde::<Vec<u8>>(&nelf).is_err());
+        assert!(from_nelf_serde::<String>(&nelf).is_err());
+    }
+}
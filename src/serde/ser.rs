@@ -0,0 +1,338 @@
+use ::serde::ser::{self, Impossible, Serialize};
+
+use crate::encode_cell_append;
+use crate::serde::SerdeError;
+
+/// Serializes the value as a NELF list, stringifying each of its elements.
+///
+/// Only sequences, tuples and tuple structs of primitives are supported,
+/// nested in any number of newtype structs. The elements are encoded as
+/// follows:
+///
+/// * Strings, characters and byte arrays as their bytes.
+/// * Booleans and numbers as their textual representation.
+/// * Unit variants as the name of the variant.
+/// * `None`, unit and unit structs as empty cells.
+/// * `Some` and newtype structs as the value they contain.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+pub fn to_nelf_serde<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<Vec<u8>, SerdeError> {
+    let mut serializer = Serializer { output: Vec::new() };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+struct Serializer {
+    output: Vec<u8>,
+}
+
+fn unsupported<T>(what: &str) -> Result<T, SerdeError> {
+    Err(SerdeError(format!(
+        "{what} cannot be serialized as a NELF list"
+    )))
+}
+
+macro_rules! unsupported {
+    ($($method:ident($($ty:ty),*) -> $ok:ty: $what:literal,)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<$ok, SerdeError> {
+                unsupported($what)
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for &mut Serializer {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = Impossible<(), SerdeError>;
+    type SerializeStruct = Impossible<(), SerdeError>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    unsupported! {
+        serialize_bool(bool) -> (): "A boolean",
+        serialize_i8(i8) -> (): "An integer",
+        serialize_i16(i16) -> (): "An integer",
+        serialize_i32(i32) -> (): "An integer",
+        serialize_i64(i64) -> (): "An integer",
+        serialize_i128(i128) -> (): "An integer",
+        serialize_u8(u8) -> (): "An integer",
+        serialize_u16(u16) -> (): "An integer",
+        serialize_u32(u32) -> (): "An integer",
+        serialize_u64(u64) -> (): "An integer",
+        serialize_u128(u128) -> (): "An integer",
+        serialize_f32(f32) -> (): "A float",
+        serialize_f64(f64) -> (): "A float",
+        serialize_char(char) -> (): "A character",
+        serialize_str(&str) -> (): "A string",
+        serialize_bytes(&[u8]) -> (): "A byte array",
+        serialize_none() -> (): "An option",
+        serialize_unit() -> (): "A unit",
+        serialize_unit_struct(&'static str) -> (): "A unit struct",
+        serialize_unit_variant(&'static str, u32, &'static str) -> ():
+            "An enum",
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant: "An enum",
+        serialize_map(Option<usize>) -> Self::SerializeMap: "A map",
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct:
+            "A struct",
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant: "An enum",
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        _: &T,
+    ) -> Result<(), SerdeError> {
+        unsupported("An option")
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), SerdeError> {
+        unsupported("An enum")
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self, SerdeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, SerdeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self, SerdeError> {
+        Ok(self)
+    }
+}
+
+impl Serializer {
+    fn push<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        let cell = value.serialize(CellSerializer)?;
+        encode_cell_append(&cell, &mut self.output);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for &mut Serializer {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut Serializer {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Serializer {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+struct CellSerializer;
+
+macro_rules! display {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Vec<u8>, SerdeError> {
+                Ok(v.to_string().into_bytes())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for CellSerializer {
+    type Ok = Vec<u8>;
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<Vec<u8>, SerdeError>;
+    type SerializeTuple = Impossible<Vec<u8>, SerdeError>;
+    type SerializeTupleStruct = Impossible<Vec<u8>, SerdeError>;
+    type SerializeTupleVariant = Impossible<Vec<u8>, SerdeError>;
+    type SerializeMap = Impossible<Vec<u8>, SerdeError>;
+    type SerializeStruct = Impossible<Vec<u8>, SerdeError>;
+    type SerializeStructVariant = Impossible<Vec<u8>, SerdeError>;
+
+    display! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    unsupported! {
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant: "A nested enum",
+        serialize_seq(Option<usize>) -> Self::SerializeSeq:
+            "A nested sequence",
+        serialize_tuple(usize) -> Self::SerializeTuple: "A nested tuple",
+        serialize_tuple_struct(&'static str, usize)
+            -> Self::SerializeTupleStruct: "A nested tuple struct",
+        serialize_map(Option<usize>) -> Self::SerializeMap: "A nested map",
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct:
+            "A nested struct",
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant: "A nested enum",
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Vec<u8>, SerdeError> {
+        Ok(v.to_vec())
+    }
+
+    fn serialize_none(self) -> Result<Vec<u8>, SerdeError> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> Result<Vec<u8>, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Vec<u8>, SerdeError> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _: &'static str,
+    ) -> Result<Vec<u8>, SerdeError> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Vec<u8>, SerdeError> {
+        Ok(variant.as_bytes().to_vec())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Vec<u8>, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Vec<u8>, SerdeError> {
+        unsupported("A nested enum")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::NelfIter;
+
+    use super::to_nelf_serde;
+
+    #[test]
+    fn to_nelf_serde_1() {
+        let nelf =
+            to_nelf_serde(&vec!["A".to_string(), "B|C".to_string()]).unwrap();
+        assert_eq!(
+            NelfIter::from_string(&nelf).collect::<Vec<_>>(),
+            [&b"A"[..], b"B|C"]
+        );
+    }
+
+    #[test]
+    fn to_nelf_serde_2() {
+        let nelf = to_nelf_serde(&(1, -2.5, 'A', true, None::<u8>)).unwrap();
+        assert_eq!(
+            NelfIter::from_string(&nelf).collect::<Vec<_>>(),
+            [&b"1"[..], b"-2.5", b"A", b"true", b""]
+        );
+    }
+
+    #[test]
+    fn to_nelf_serde_3() {
+        assert!(to_nelf_serde("A").is_err());
+        assert!(to_nelf_serde(&[[1]]).is_err());
+        assert!(to_nelf_serde(&HashMap::<u8, u8>::new()).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+use ::smallvec::SmallVec;
+
+use crate::NelfIter;
+
+/// Decodes the cells of the list, keeping up to `N` of them inline.
+///
+/// Short lists are thus collected without allocating, unlike with
+/// `collect::<Vec<_>>()`.
+pub fn decode_smallvec<const N: usize>(string: &[u8]) -> SmallVec<[&[u8]; N]> {
+    NelfIter::from_string(string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ToNelf;
+
+    use super::decode_smallvec;
+
+    #[test]
+    fn decode_smallvec_1() {
+        let string = [b"A", b"B"].to_nelf();
+        let cells = decode_smallvec::<4>(&string);
+        assert!(!cells.spilled());
+        assert_eq!(&cells[..], [b"A", b"B"]);
+
+        let string = [b"A", b"B", b"C", b"D", b"E"].to_nelf();
+        let cells = decode_smallvec::<4>(&string);
+        assert!(cells.spilled());
+        assert_eq!(&cells[..], [b"A", b"B", b"C", b"D", b"E"]);
+    }
+}
@@ -0,0 +1,39 @@
+//! Strategies for property testing with proptest.
+
+use proptest::collection::vec;
+use proptest::prelude::{any, Strategy};
+
+use crate::ToNelf;
+
+/// Strategy generating a list of cells along with its encoding.
+///
+/// Cells are biased towards the delimiter bytes, so that the generated lists
+/// exercise runs of various lengths.
+pub fn nelf_strategy() -> impl Strategy<Value = (Vec<Vec<u8>>, Vec<u8>)> {
+    let byte = proptest::prop_oneof![
+        proptest::sample::select(&b"|/\\"[..]),
+        any::<u8>(),
+    ];
+
+    vec(vec(byte, 0..16), 0..16).prop_map(|cells| {
+        let string = (&cells).to_nelf();
+        (cells, string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use crate::{validate, NelfIter};
+
+    use super::nelf_strategy;
+
+    proptest! {
+        #[test]
+        fn nelf_strategy_1((cells, string) in nelf_strategy()) {
+            assert_eq!(validate(&string), Ok(()));
+            assert_eq!(NelfIter::from_string(&string).collect::<Vec<_>>(), cells);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use either::Either;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::private::CellStreamExtSealed;
+
+/// Extension trait for streams of cells, such as a `Framed` stream using
+/// `NelfCodec`.
+///
+/// Implemented for all streams of `io::Result<Vec<u8>>`. Sealed.
+pub trait CellStreamExt: CellStreamExtSealed {
+    /// Decodes each cell of the stream with the given function.
+    ///
+    /// Errors of the stream are yielded as [`Either::Left`], errors of the
+    /// function as [`Either::Right`].
+    ///
+    /// [`Either::Left`]: either::Either::Left
+    /// [`Either::Right`]: either::Either::Right
+    fn map_cells<T, E, F>(self, f: F) -> MapCells<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Vec<u8>) -> Result<T, E>,
+    {
+        MapCells { stream: self, f }
+    }
+}
+
+impl<S: Stream<Item = io::Result<Vec<u8>>>> CellStreamExtSealed for S {}
+
+impl<S: Stream<Item = io::Result<Vec<u8>>>> CellStreamExt for S {}
+
+pin_project! {
+    /// Stream returned by [`CellStreamExt::map_cells`].
+    ///
+    /// [`CellStreamExt::map_cells`]: CellStreamExt::map_cells
+    #[must_use = "streams do nothing unless polled"]
+    pub struct MapCells<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+    }
+}
+
+impl<S, F, T, E> Stream for MapCells<S, F>
+where
+    S: Stream<Item = io::Result<Vec<u8>>>,
+    F: FnMut(Vec<u8>) -> Result<T, E>,
+{
+    type Item = Result<T, Either<io::Error, E>>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        this.stream.poll_next(cx).map(|cell| {
+            cell.map(|cell| match cell {
+                Ok(cell) => (this.f)(cell).map_err(Either::Right),
+                Err(error) => Err(Either::Left(error)),
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, ErrorKind};
+    use std::str;
+
+    use either::Either;
+    use futures::StreamExt;
+
+    use super::CellStreamExt;
+
+    fn parse(cell: Vec<u8>) -> Result<u32, String> {
+        str::from_utf8(&cell)
+            .map_err(|error| error.to_string())?
+            .parse()
+            .map_err(|error: std::num::ParseIntError| error.to_string())
+    }
+
+    #[tokio::test]
+    async fn map_cells_1() {
+        let cells = futures::stream::iter([
+            Ok(b"1".to_vec()),
+            Ok(b"23".to_vec()),
+            Ok(b"A".to_vec()),
+            Err(io::Error::from(ErrorKind::UnexpectedEof)),
+        ]);
+        let result: Vec<_> = cells.map_cells(parse).collect().await;
+
+        assert!(matches!(result[0], Ok(1)));
+        assert!(matches!(result[1], Ok(23)));
+        assert!(matches!(result[2], Err(Either::Right(_))));
+        assert!(matches!(
+            &result[3],
+            Err(Either::Left(error)) if error.kind() == ErrorKind::UnexpectedEof
+        ));
+    }
+}
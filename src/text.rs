@@ -0,0 +1,226 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::str;
+
+use crate::{encode_cell_append, NelfError, NelfIter};
+
+/// Decodes the list into owned strings.
+///
+/// Returns an error naming the first cell that is not valid UTF-8.
+pub fn decode_to_strings(string: &[u8]) -> Result<Vec<String>, NelfError> {
+    str_cells(string)
+        .map(|cell| cell.map(str::to_string))
+        .collect()
+}
+
+/// Encodes the strings as a NELF list.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+pub fn encode_from_strings<I, S>(items: I) -> Vec<u8>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut result = Vec::new();
+
+    for item in items {
+        encode_cell_append(item.as_ref().as_bytes(), &mut result);
+    }
+
+    result
+}
+
+/// Renders the cells of the list one per line, for display purposes.
+///
+/// Bytes other than printable ASCII are shown as C-style escapes, as is the
+/// backslash itself, so every line of the output corresponds to exactly one
+/// cell. Every line, including the last one, ends with a newline, so an empty
+/// cell at the end of the list is still visible.
+///
+/// The output is meant to be read by people and is not a NELF list. Pipes
+/// and slashes inside cells are printed as they are, so it is only
+/// guaranteed to round-trip through [`from_display_lines`].
+///
+/// [`from_display_lines`]: from_display_lines
+pub fn to_display_lines(string: &[u8]) -> String {
+    let mut result = String::new();
+
+    for cell in NelfIter::from_string(string) {
+        for &byte in cell {
+            match byte {
+                b'\\' => result.push_str("\\\\"),
+                b'\0' => result.push_str("\\0"),
+                b'\t' => result.push_str("\\t"),
+                b'\n' => result.push_str("\\n"),
+                b'\r' => result.push_str("\\r"),
+                b' '..=b'~' => result.push(byte as char),
+                _ => write!(result, "\\x{byte:02x}").unwrap(),
+            }
+        }
+
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Encodes each line of the text as a cell, interpreting the escapes produced
+/// by [`to_display_lines`].
+///
+/// Lines end with `\n` or `\r\n`, and the newline after the last line is
+/// optional, so every output of [`to_display_lines`] decodes back to the same
+/// list, empty cells included. Empty text decodes to an empty list.
+///
+/// Backslashes not followed by a valid escape are kept as they are.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+/// [`to_display_lines`]: to_display_lines
+pub fn from_display_lines(text: &str) -> Vec<u8> {
+    let mut result = Vec::new();
+
+    for line in text.split_terminator('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line).as_bytes();
+        let mut cell = Vec::with_capacity(line.len());
+        let mut index = 0;
+
+        while index < line.len() {
+            let (byte, len) = match line[index..] {
+                [b'\\', b'\\', ..] => (b'\\', 2),
+                [b'\\', b'0', ..] => (b'\0', 2),
+                [b'\\', b't', ..] => (b'\t', 2),
+                [b'\\', b'n', ..] => (b'\n', 2),
+                [b'\\', b'r', ..] => (b'\r', 2),
+                [b'\\', b'x', high, low, ..]
+                    if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() =>
+                {
+                    let hex = str::from_utf8(&line[index + 2..index + 4]);
+                    (u8::from_str_radix(hex.unwrap(), 16).unwrap(), 4)
+                }
+                [byte, ..] => (byte, 1),
+                [] => unreachable!(),
+            };
+
+            cell.push(byte);
+            index += len;
+        }
+
+        encode_cell_append(&cell, &mut result);
+    }
+
+    result
+}
+
+/// Iterates over the cells of the list, checking they are valid UTF-8.
+pub(crate) fn str_cells(
+    string: &[u8],
+) -> impl Iterator<Item = Result<&str, NelfError>> {
+    NelfIter::from_string(string)
+        .enumerate()
+        .map(|(index, cell)| {
+            str::from_utf8(cell).map_err(|_| NelfError::InvalidUtf8 { index })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::{NelfError, ToCell, ToNelf};
+
+    use super::{
+        decode_to_strings, encode_from_strings, from_display_lines,
+        to_display_lines,
+    };
+
+    #[test]
+    fn decode_to_strings_1() {
+        assert_eq!(
+            decode_to_strings(
+                &[&b"A"[..], b"B|C", "\u{e9}".as_bytes()].to_nelf()
+            )
+            .unwrap(),
+            ["A", "B|C", "\u{e9}"]
+        );
+        assert_eq!(decode_to_strings(b"").unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn decode_to_strings_2() {
+        assert_eq!(
+            decode_to_strings(b"|A||B||\xff||C|"),
+            Err(NelfError::InvalidUtf8 { index: 2 })
+        );
+    }
+
+    #[test]
+    fn encode_from_strings_1() {
+        let nelf = encode_from_strings(["A", "B|C"]);
+        assert_eq!(decode_to_strings(&nelf).unwrap(), ["A", "B|C"]);
+        let nelf = encode_from_strings(vec![String::from("/"), String::new()]);
+        assert_eq!(decode_to_strings(&nelf).unwrap(), ["/", ""]);
+    }
+
+    #[test]
+    fn to_display_lines_1() {
+        assert_eq!(to_display_lines(b"|A||B|"), "A\nB\n");
+        assert_eq!(
+            to_display_lines(
+                &[&b"A\nB"[..], b"\\|/", b"\0\t\r\x7f\xff"].to_nelf()
+            ),
+            "A\\nB\n\\\\|/\n\\0\\t\\r\\x7f\\xff\n"
+        );
+        assert_eq!(to_display_lines(b""), "");
+        assert_eq!(to_display_lines(b"/\\"), "\n");
+        assert_eq!(to_display_lines(b"|A|/\\"), "A\n\n");
+    }
+
+    #[test]
+    fn from_display_lines_1() {
+        assert_eq!(to_display_lines(&from_display_lines("A\nB|C")), "A\nB|C\n");
+        assert_eq!(from_display_lines("A\r\nB\n"), [&b"A"[..], b"B"].to_nelf());
+        assert_eq!(
+            from_display_lines("A\\nB\n\\\\|/\n\\0\\t\\r\\x7f\\xff"),
+            [&b"A\nB"[..], b"\\|/", b"\0\t\r\x7f\xff"].to_nelf()
+        );
+        assert_eq!(
+            from_display_lines("\\q\\x4\\x+1\\xzz\\"),
+            b"\\q\\x4\\x+1\\xzz\\".to_cell()
+        );
+    }
+
+    #[test]
+    fn from_display_lines_delimiters() {
+        let nelf = [&b"|"[..], b"/", b"\\", b"A|B/C\\D", b"||//\\\\", b"\\n"]
+            .to_nelf();
+        assert_eq!(from_display_lines(&to_display_lines(&nelf)), nelf);
+    }
+
+    #[test]
+    fn from_display_lines_empty_cells() {
+        for list in [
+            &[][..],
+            &[&b""[..]],
+            &[&b"A"[..], b""],
+            &[&b""[..], b""],
+            &[&b""[..], b"A", b""],
+        ] {
+            let nelf = list.iter().copied().to_nelf();
+            assert_eq!(from_display_lines(&to_display_lines(&nelf)), nelf);
+        }
+        assert_eq!(from_display_lines(""), b"");
+        assert_eq!(from_display_lines("\n"), b"".to_cell());
+    }
+}
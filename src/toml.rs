@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use ::toml::Value;
+
+use crate::text::str_cells;
+use crate::{encode_cell_append, NelfError};
+
+/// Error returned when a TOML value is not an array of strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TomlError {
+    /// The value is not an array.
+    NotAnArray,
+    /// The element at the given index is not a string.
+    NotAString {
+        /// Index of the element in the array.
+        index: usize,
+    },
+}
+
+impl Display for TomlError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TomlError::NotAnArray => f.write_str("TOML value is not an array"),
+            TomlError::NotAString { index } => {
+                write!(f, "TOML array element {index} is not a string")
+            }
+        }
+    }
+}
+
+impl Error for TomlError {}
+
+/// Converts a TOML array of strings into a NELF list.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+pub fn from_toml_array(toml_value: &Value) -> Result<Vec<u8>, TomlError> {
+    let mut result = Vec::new();
+
+    for (index, item) in toml_value
+        .as_array()
+        .ok_or(TomlError::NotAnArray)?
+        .iter()
+        .enumerate()
+    {
+        let item = item.as_str().ok_or(TomlError::NotAString { index })?;
+        encode_cell_append(item.as_bytes(), &mut result);
+    }
+
+    Ok(result)
+}
+
+/// Converts the list into a TOML array of strings.
+///
+/// Returns an error if any of the cells is not valid UTF-8.
+pub fn to_toml_array(string: &[u8]) -> Result<Value, NelfError> {
+    str_cells(string)
+        .map(|cell| cell.map(|cell| Value::String(cell.to_string())))
+        .collect::<Result<_, _>>()
+        .map(Value::Array)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::toml::{Table, Value};
+
+    use crate::{NelfError, ToNelf};
+
+    use super::{from_toml_array, to_toml_array, TomlError};
+
+    #[test]
+    fn toml_array_1() {
+        let table: Table = r#"list = ["A", "B|C", ""]"#.parse().unwrap();
+        let nelf = from_toml_array(&table["list"]).unwrap();
+        assert_eq!(nelf, [&b"A"[..], b"B|C", b""].to_nelf());
+        assert_eq!(to_toml_array(&nelf).unwrap(), table["list"]);
+    }
+
+    #[test]
+    fn toml_array_2() {
+        let table: Table = r#"list = ["A", 1]"#.parse().unwrap();
+        assert_eq!(
+            from_toml_array(&table["list"]),
+            Err(TomlError::NotAString { index: 1 })
+        );
+        assert_eq!(
+            from_toml_array(&Value::Integer(1)),
+            Err(TomlError::NotAnArray)
+        );
+        assert_eq!(
+            to_toml_array(b"|A||\xff|"),
+            Err(NelfError::InvalidUtf8 { index: 1 })
+        );
+    }
+}
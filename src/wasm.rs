@@ -0,0 +1,85 @@
+//! Bindings for JavaScript through `wasm-bindgen`.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::str;
+
+use js_sys::{Array, Error, JsString, Uint8Array};
+use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+
+use crate::encode::encode_cell;
+use crate::{NelfError, NelfIter};
+
+/// Encodes the cells as a NELF list.
+///
+/// # Panics
+///
+/// Panics if the length of the list overflows `usize`, like
+/// [`ToNelf::to_nelf`].
+///
+/// [`ToNelf::to_nelf`]: crate::ToNelf::to_nelf
+#[wasm_bindgen]
+pub fn encode(cells: Vec<Uint8Array>) -> Vec<u8> {
+    let mut result = Vec::new();
+
+    for cell in cells {
+        encode_cell(&cell.to_vec(), &mut result);
+    }
+
+    result
+}
+
+/// Decodes the cells of the list, copying each of them into a `Uint8Array`.
+#[wasm_bindgen]
+pub fn decode(buf: &[u8]) -> Array {
+    NelfIter::from_string(buf).map(Uint8Array::from).collect()
+}
+
+/// Decodes the cells of the list as strings.
+///
+/// Throws an `Error` if any of the cells is not valid UTF-8.
+#[wasm_bindgen]
+pub fn decode_strings(buf: &[u8]) -> Result<Array, JsValue> {
+    NelfIter::from_string(buf)
+        .enumerate()
+        .map(|(index, cell)| match str::from_utf8(cell) {
+            Ok(cell) => Ok(JsString::from(cell)),
+            Err(_) => {
+                let error = NelfError::InvalidUtf8 { index };
+                Err(Error::new(&error.to_string()).into())
+            }
+        })
+        .collect()
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use js_sys::Uint8Array;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{decode, decode_strings, encode};
+
+    #[wasm_bindgen_test]
+    fn wasm_round_trip() {
+        let cells = [&b"A"[..], b"/|\\", b""];
+        let string = encode(cells.map(Uint8Array::from).to_vec());
+        let decoded: Vec<_> = decode(&string)
+            .iter()
+            .map(|cell| Uint8Array::new(&cell).to_vec())
+            .collect();
+        assert_eq!(decoded, cells);
+    }
+
+    #[wasm_bindgen_test]
+    fn wasm_decode_strings() {
+        let strings: Vec<_> = decode_strings(b"|A|/|\\/\\")
+            .unwrap()
+            .iter()
+            .map(|string| string.as_string().unwrap())
+            .collect();
+        assert_eq!(strings, ["A", "|", ""]);
+        assert!(decode_strings(b"|A||\xFF|").is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::max_runs;
+use crate::scan::IS_DELIM;
+
+/// Opening and closing delimiters cells can be wrapped with, in the order of
+/// preference.
+const WRAPPERS: [(u8, u8); 3] = [(b'|', b'|'), (b'/', b'\\'), (b'\\', b'/')];
+
+/// Chooses the delimiters to wrap the content with, returning the opening
+/// byte, the closing byte and the length of the runs.
+///
+/// The runs are at most one byte longer than the content, which can't be
+/// longer than `isize::MAX`, so choosing them never overflows.
+pub(crate) fn wrapper(content: &[u8]) -> (u8, u8, usize) {
+    if content.is_empty() {
+        return (b'/', b'\\', 1);
+    }
+
+    if !content.iter().any(|&ch| IS_DELIM[ch as usize]) {
+        return (b'|', b'|', 1);
+    }
+
+    let [pipes, slashes, backslashes] = max_runs(content);
+    let mut lens = [pipes + 1, backslashes + 1, slashes + 1];
+
+    for (len, (open, close)) in lens.iter_mut().zip(WRAPPERS) {
+        if content.first() == Some(&open) || content.last() == Some(&close) {
+            *len = usize::MAX;
+        }
+    }
+
+    // The first of the equally short runs is chosen, following the order of
+    // preference of the delimiters.
+    let index = (0..WRAPPERS.len())
+        .min_by_key(|&index| lens[index])
+        .unwrap();
+    let (open, close) = WRAPPERS[index];
+
+    (open, close, lens[index])
+}
+
+/// Returns the length of the cell the content is encoded as.
+///
+/// # Panics
+///
+/// Panics if the length overflows `usize`, which is only possible for
+/// content longer than a third of the address space. See
+/// [`checked_encoded_len`] for a version returning `None` instead.
+///
+/// [`checked_encoded_len`]: checked_encoded_len
+pub fn encoded_len(content: &[u8]) -> usize {
+    checked_encoded_len(content).expect("encoded length overflow")
+}
+
+/// Returns the length of the cell the content is encoded as, or `None` if it
+/// overflows `usize`.
+pub fn checked_encoded_len(content: &[u8]) -> Option<usize> {
+    cell_len(content, wrapper(content))
+}
+
+/// Returns the length of the cell the content is encoded as with the
+/// delimiters, or `None` if it overflows `usize`.
+pub(crate) fn cell_len(
+    content: &[u8],
+    (_, _, len): (u8, u8, usize),
+) -> Option<usize> {
+    len.checked_mul(2)?.checked_add(content.len())
+}
+
+/// Writes the content wrapped with the delimiters into the cell, which has
+/// to be exactly as long as the encoded content.
+pub(crate) fn write_cell(
+    content: &[u8],
+    (open, close, len): (u8, u8, usize),
+    cell: &mut [u8],
+) {
+    let (start, rest) = cell.split_at_mut(len);
+    let (middle, end) = rest.split_at_mut(content.len());
+
+    start.fill(open);
+    middle.copy_from_slice(content);
+    end.fill(close);
+}
+
+/// Encodes the cells as a NELF list into the fixed buffer, without
+/// allocating.
+///
+/// Returns the number of bytes written, or the length the buffer would have
+/// to be if the list doesn't fit into it, in which case the contents of the
+/// buffer are unspecified. The required length saturates at `usize::MAX`
+/// rather than overflowing.
+pub fn encode_list_buf(
+    cells: &[&[u8]],
+    out: &mut [u8],
+) -> Result<usize, usize> {
+    let mut pos = 0;
+
+    for (index, &cell) in cells.iter().enumerate() {
+        let wrapper = wrapper(cell);
+        let len = cell_len(cell, wrapper).unwrap_or(usize::MAX);
+
+        if out.len() - pos < len {
+            let required = cells[index..].iter().fold(pos, |required, cell| {
+                let len = cell_len(cell, self::wrapper(cell));
+                required.saturating_add(len.unwrap_or(usize::MAX))
+            });
+            return Err(required);
+        }
+
+        write_cell(cell, wrapper, &mut out[pos..pos + len]);
+        pos += len;
+    }
+
+    Ok(pos)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+
+    #[cfg(feature = "alloc")]
+    use crate::max_runs;
+    use crate::NelfIter;
+
+    use super::{cell_len, checked_encoded_len, encode_list_buf, encoded_len};
+    #[cfg(feature = "alloc")]
+    use super::{wrapper, WRAPPERS};
+
+    /// The way the delimiters were chosen before the table of delimiters was
+    /// introduced.
+    #[cfg(feature = "alloc")]
+    fn wrapper_reference(content: &[u8]) -> (u8, u8, usize) {
+        if content.is_empty() {
+            return (b'/', b'\\', 1);
+        }
+
+        let mut pipe = true;
+        let mut forward = true;
+        let mut back = true;
+
+        match content.first().unwrap() {
+            b'|' => pipe = false,
+            b'/' => forward = false,
+            b'\\' => back = false,
+            _ => (),
+        }
+
+        match content.last().unwrap() {
+            b'|' => pipe = false,
+            b'/' => back = false,
+            b'\\' => forward = false,
+            _ => (),
+        }
+
+        let [pipes, slashes, backslashes] = max_runs(content);
+
+        let pipe_max = if pipe { pipes + 1 } else { usize::MAX };
+        let forward_max = if forward { backslashes + 1 } else { usize::MAX };
+        let back_max = if back { slashes + 1 } else { usize::MAX };
+
+        let min = pipe_max.min(forward_max).min(back_max);
+
+        if pipe_max == min {
+            (b'|', b'|', min)
+        } else if forward_max == min {
+            (b'/', b'\\', min)
+        } else {
+            (b'\\', b'/', min)
+        }
+    }
+
+    /// Every string of up to `max_len` bytes from the alphabet.
+    #[cfg(feature = "alloc")]
+    fn contents(alphabet: &[u8], max_len: u32) -> Vec<Vec<u8>> {
+        (0..=max_len)
+            .flat_map(|len| {
+                (0..alphabet.len().pow(len)).map(move |mut code| {
+                    (0..len)
+                        .map(|_| {
+                            let ch = alphabet[code % alphabet.len()];
+                            code /= alphabet.len();
+                            ch
+                        })
+                        .collect()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn wrapper_parity() {
+        for content in contents(b"|/\\A", 7) {
+            assert_eq!(wrapper(&content), wrapper_reference(&content));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn wrapper_minimal() {
+        for content in contents(b"|/\\A", 6) {
+            // The shortest runs of any delimiters the content round-trips
+            // with, trying the delimiters in the order of preference.
+            let minimal = WRAPPERS
+                .iter()
+                .flat_map(|&(open, close)| {
+                    (1..=content.len() + 1).map(move |len| (open, close, len))
+                })
+                .filter(|&(open, close, len)| {
+                    let cell = [
+                        &[open].repeat(len)[..],
+                        &content,
+                        &[close].repeat(len),
+                    ]
+                    .concat();
+                    let mut iter = NelfIter::from_string(&cell);
+                    iter.next() == Some(&content[..]) && iter.next().is_none()
+                })
+                .min_by_key(|&(_, _, len)| len)
+                .unwrap();
+
+            assert_eq!(wrapper(&content), minimal, "{content:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn wrapper_exceeds_interior_runs() {
+        for content in contents(b"|/\\A", 7) {
+            let (open, close, len) = wrapper(&content);
+            let max_run = content
+                .split(|&ch| ch != close)
+                .map(<[u8]>::len)
+                .max()
+                .unwrap_or(0);
+
+            assert!(max_run < len, "{content:?}");
+            assert_ne!(content.first(), Some(&open), "{content:?}");
+            assert_ne!(content.last(), Some(&close), "{content:?}");
+        }
+    }
+
+    #[test]
+    fn encode_list_buf_1() {
+        let cells = [&b"A"[..], b"/|\\", b"", b"||A"];
+        let len = cells.iter().map(|cell| encoded_len(cell)).sum();
+        let mut out = [0; 64];
+
+        assert_eq!(encode_list_buf(&cells, &mut out), Ok(len));
+        assert!(NelfIter::from_string(&out[..len]).eq(cells));
+        assert_eq!(encode_list_buf(&cells, &mut out[..len]), Ok(len));
+        assert_eq!(encode_list_buf(&cells, &mut out[..len - 1]), Err(len));
+        assert_eq!(encode_list_buf(&cells, &mut []), Err(len));
+        assert_eq!(encode_list_buf(&[], &mut []), Ok(0));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn wrapper_long_runs() {
+        let run = 1 << 20;
+        let content: Vec<_> = [b'A', b'|', b'/', b'\\', b'A']
+            .iter()
+            .flat_map(|&ch| vec![ch; if ch == b'A' { 1 } else { run }])
+            .collect();
+        assert_eq!(wrapper(&content), (b'|', b'|', run + 1));
+        assert_eq!(wrapper(&vec![b'|'; run]), (b'/', b'\\', 1));
+
+        let len = encoded_len(&content);
+        let mut out = vec![0; len];
+        assert_eq!(encode_list_buf(&[&content], &mut out), Ok(len));
+        assert_eq!(NelfIter::from_string(&out).collect::<Vec<_>>(), [content]);
+    }
+
+    #[test]
+    fn cell_len_overflow() {
+        assert_eq!(cell_len(b"A", (b'|', b'|', 1)), Some(3));
+        assert_eq!(
+            cell_len(b"", (b'|', b'|', usize::MAX / 2)),
+            Some(usize::MAX - 1)
+        );
+        assert_eq!(cell_len(b"A", (b'|', b'|', usize::MAX / 2 + 1)), None);
+        assert_eq!(cell_len(b"AA", (b'|', b'|', usize::MAX / 2)), None);
+        assert_eq!(checked_encoded_len(b"/|"), Some(6));
+        assert_eq!(checked_encoded_len(b""), Some(2));
+    }
+}
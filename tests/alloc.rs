@@ -0,0 +1,62 @@
+#![cfg(feature = "alloc")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use nelf::{encode_list, Nelf, ToNelf};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn encode_list_allocates_once() {
+    let cells: Vec<&[u8]> =
+        (0..1000).map(|i| &b"A|B//C\\\\D"[..i % 10]).collect();
+
+    let (result, count) = allocations(|| encode_list(cells.iter().copied()));
+    assert_eq!(count, 1);
+    assert_eq!(result, cells.to_nelf());
+}
+
+#[test]
+fn nelf_iter_collect_allocates_once() {
+    let nelf = Nelf::from_cells((0..1000).map(|i| (i as u32).to_be_bytes()));
+
+    let (cells, count) = allocations(|| nelf.iter().collect::<Vec<_>>());
+    assert_eq!(count, 1);
+    assert_eq!(cells.len(), 1000);
+    assert_eq!(cells[999], 999u32.to_be_bytes());
+}
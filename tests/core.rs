@@ -0,0 +1,68 @@
+use nelf::{validate, NelfError, NelfIter};
+
+static LIST: &[u8] = b"|A| /|\\ \\C/ |/| /\\";
+
+#[test]
+fn static_list() {
+    let mut iter = NelfIter::from_string(LIST);
+    assert_eq!(iter.next(), Some(&b"A"[..]));
+    assert_eq!(iter.next(), Some(&b"|"[..]));
+    assert_eq!(iter.next(), Some(&b"C"[..]));
+    assert_eq!(iter.next(), Some(&b"/"[..]));
+    assert_eq!(iter.next(), Some(&b""[..]));
+    assert_eq!(iter.next(), None);
+    assert_eq!(validate(LIST), Ok(()));
+}
+
+struct FixedBuf {
+    buf: [u8; 64],
+    len: usize,
+}
+
+impl core::fmt::Write for FixedBuf {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn error_display() {
+    use core::fmt::Write;
+
+    let error = validate(b"|A||B").unwrap_err();
+    assert_eq!(error, NelfError::Unterminated { index: 1 });
+
+    let mut buf = FixedBuf {
+        buf: [0; 64],
+        len: 0,
+    };
+    write!(buf, "{error}").unwrap();
+    assert_eq!(&buf.buf[..buf.len], b"cell 1 is not terminated");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn error_trait() {
+    let error: &dyn std::error::Error = &NelfError::Unterminated { index: 1 };
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn validate_without_alloc() {
+    assert_eq!(validate(b"|A|/B\\"), Ok(()));
+    assert_eq!(validate(b""), Ok(()));
+    assert_eq!(
+        validate(b"|A|/B"),
+        Err(NelfError::Unterminated { index: 1 })
+    );
+    assert_eq!(
+        validate(b"|A|//"),
+        Err(NelfError::Unterminated { index: 1 })
+    );
+}
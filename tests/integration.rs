@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 use nelf::{NelfIter, ToNelf};
 
 #[test]